@@ -0,0 +1,88 @@
+//! Sanitizer benchmarks. Run with `cargo bench`, adding features to measure
+//! larger range tables, e.g. `cargo bench --features cyrillic,arabic`.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use langsan::{ranges::BLOCKS, sanitize, sanitize_with, Policy};
+
+/// A `len` byte string made by repeating `unit`, truncated to a character
+/// boundary.
+fn repeat(unit: &str, len: usize) -> String {
+    let mut s = unit.repeat(len / unit.len() + 1);
+    let mut end = len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s.truncate(end);
+    s
+}
+
+const ASCII: &str = "The quick brown fox jumps over the lazy dog.\n";
+const MIXED: &str = "Grüße, Привет, مرحبا, こんにちは, 你好 👋 naïve café\t";
+
+fn ascii(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ascii");
+    for len in [64, 4 << 10, 1 << 20] {
+        let input = repeat(ASCII, len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &input, |b, input| {
+            b.iter(|| sanitize(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn mixed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mixed");
+    for len in [64, 4 << 10, 1 << 20] {
+        let input = repeat(MIXED, len);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &input, |b, input| {
+            b.iter(|| sanitize(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+/// A long stream delivered in token-sized chunks, sanitized as they arrive,
+/// and the same stream sanitized at once with a tag character at the very
+/// end, so the whole input has to be scanned and copied.
+fn streaming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("streaming");
+    let input = repeat(ASCII, 1 << 20);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    let tokens: Vec<&str> = input.split_inclusive(' ').collect();
+    group.bench_function("tokens", |b| {
+        b.iter(|| {
+            for token in &tokens {
+                black_box(sanitize(black_box(token)));
+            }
+        })
+    });
+    let tagged = input.clone() + "\u{E0041}";
+    group.bench_function("tag-at-end", |b| b.iter(|| sanitize(black_box(&tagged))));
+    group.finish();
+}
+
+/// The default policy against one allowing every Unicode block, as if every
+/// range feature were enabled.
+fn ranges(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ranges");
+    let input = repeat(MIXED, 4 << 10);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    let default = Policy::default();
+    let every_block = Policy::new(
+        BLOCKS
+            .iter()
+            .map(|(range, _)| range.clone())
+            .collect::<Vec<_>>(),
+    );
+    group.bench_function("default", |b| {
+        b.iter(|| sanitize_with(black_box(&input), &default))
+    });
+    group.bench_function("every-block", |b| {
+        b.iter(|| sanitize_with(black_box(&input), &every_block))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ascii, mixed, streaming, ranges);
+criterion_main!(benches);
@@ -1,14 +1,40 @@
-use std::path::PathBuf;
-
-/// A build script to parse unicode range json and generate a rust file with
-/// those ranges, but only if their corresponding feature is enabled.
-use serde_json;
+//! A build script to parse unicode range json and generate a rust file with
+//! those ranges, but only if their corresponding feature is enabled.
+use std::{collections::BTreeMap, path::PathBuf};
 
 const CRATE_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 /// Git submodule path to the unicode range json file.
 const JSON_PATH: &str = "unicode-range-json/unicode-ranges.json";
-/// Rust file to generate with the unicode ranges.
-const RANGES_RS: &str = "src/ranges.rs";
+/// Vendored copy of the unicode range json, used when the submodule isn't
+/// checked out, as in published crates.
+const VENDORED_JSON_PATH: &str = "data/unicode-ranges.json";
+/// The Unicode Character Database's `Blocks.txt`, used instead of the json if
+/// it's vendored, so the ranges can track the standard directly.
+const BLOCKS_TXT_PATH: &str = "data/Blocks.txt";
+/// The Unicode emoji properties, `emoji-data.txt`. The vendored copy has only
+/// the properties we use.
+const EMOJI_DATA_PATH: &str = "data/emoji-data.txt";
+/// Json mapping each language feature to the range features it enables.
+const LANGUAGES_PATH: &str = "data/languages.json";
+/// Json mapping features to individual code points they enable, for when a
+/// whole block would allow too much.
+const CODE_POINTS_PATH: &str = "data/code-points.json";
+/// Rust file to generate in `OUT_DIR` with the unicode ranges. It's included
+/// by `src/ranges.rs`.
+const RANGES_RS: &str = "ranges.rs";
+/// Rust file to generate in `OUT_DIR` describing the build. It's included by
+/// `src/info.rs`.
+const BUILD_INFO_RS: &str = "build_info.rs";
+/// Rust file to generate in `OUT_DIR` with the emoji properties. It's included
+/// by `src/emoji.rs`.
+const EMOJI_RS: &str = "emoji.rs";
+/// The properties of `emoji-data.txt` to generate tables for, and their
+/// constant names.
+const EMOJI_PROPERTIES: &[(&str, &str)] = &[
+    ("Extended_Pictographic", "EXTENDED_PICTOGRAPHIC"),
+    ("Emoji_Presentation", "EMOJI_PRESENTATION"),
+    ("Emoji_Modifier", "EMOJI_MODIFIER"),
+];
 /// Cargo.toml content, so we can generate the features
 const CARGO_TOML: &str = r#"# WARNING: This file is generated by build.rs
 [package]
@@ -44,30 +70,49 @@ verbose = []
 serde = ["dep:serde"]
 
 # Languages
-english = []
-spanish = ["latin-1-supplement"]
-french = ["latin-1-supplement"]
-german = ["latin-1-supplement"]
-italian = ["latin-1-supplement"]
-dutch = ["latin-1-supplement"]
-portuguese = ["latin-1-supplement"]
-russian = ["cyrillic"]
-emoji = ["emoticons-emoji"]
-
+"#;
+/// Cargo.toml content between the language and range features.
+const CARGO_TOML_RANGES: &str = r#"
 # Unicode ranges. Note that whitespace and basic-latin are enabled by default.
-# "tags" are included for completion sake but very much not recommended for use.
+# "tags" are included for completion sake but very much not recommended for use,
+# and are removed anyway unless "dangerous-allow-tags" is enabled.
 "#;
 
+/// Language features and the range features each enables.
+type Languages = BTreeMap<String, Vec<String>>;
+/// Features and the code points each enables.
+type CodePoints = BTreeMap<String, Vec<NamedRange>>;
+
 #[derive(serde::Deserialize)]
 struct NamedRange {
     category: String,
     range: [u32; 2],
 }
 
-/// Returns `(ranges.rs, Cargo.toml)`. We have a lot of features to generate
-/// so we don't want to write them all out
-fn gen_ranges(json: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
-    let ranges: Vec<NamedRange> = serde_json::from_str(json)?;
+/// Returns `(ranges.rs, Cargo.toml, features)`. We have a lot of features to
+/// generate so we don't want to write them all out. The `custom` json, in the
+/// same format as the unicode range json, is always enabled.
+fn gen_ranges(
+    ranges: &[NamedRange],
+    unicode_version: Option<&str>,
+    custom: Option<&str>,
+    languages: &Languages,
+    code_points: &CodePoints,
+) -> Result<(String, String, Vec<String>), Box<dyn std::error::Error>> {
+    let custom: Vec<NamedRange> = match custom {
+        Some(custom) => serde_json::from_str(custom)?,
+        None => Vec::new(),
+    };
+    if let Some(range) = custom
+        .iter()
+        .find(|r| r.range[0] > r.range[1] || r.range[1] > char::MAX as u32)
+    {
+        return Err(format!(
+            "invalid custom range {:?} [{}, {}]",
+            range.category, range.range[0], range.range[1]
+        )
+        .into());
+    }
     let features: Vec<String> = ranges
         .iter()
         .map(|range| {
@@ -75,8 +120,7 @@ fn gen_ranges(json: &str) -> Result<(String, String), Box<dyn std::error::Error>
                 .category
                 .to_lowercase()
                 .replace(' ', "-")
-                .replace('(', "")
-                .replace(')', "")
+                .replace(['(', ')'], "")
         })
         .collect();
     let const_names: Vec<String> = features
@@ -84,9 +128,13 @@ fn gen_ranges(json: &str) -> Result<(String, String), Box<dyn std::error::Error>
         .map(|feature| feature.to_uppercase().replace('-', "_"))
         .collect();
     let mut cargo_toml = CARGO_TOML.to_string();
+    for (language, deps) in languages {
+        let deps: Vec<String> = deps.iter().map(|d| format!("{d:?}")).collect();
+        cargo_toml.push_str(&format!("{language} = [{}]\n", deps.join(", ")));
+    }
+    cargo_toml.push_str(CARGO_TOML_RANGES);
     let mut code = r#"// WARNING: This file is generated by build.rs
 // Do not modify this file directly.
-/// Unicode ranges
 use core::ops::RangeInclusive;
 
 // Constants for unicode ranges
@@ -97,6 +145,11 @@ pub const WHITESPACE: RangeInclusive<u32> = 0x00009..=0x0000C;
 pub const BASIC_LATIN: RangeInclusive<u32> = 0x00020..=0x0007E; // 0x7F is DEL
 "#
     .to_string();
+    code.push_str(&format!(
+        "/// The version of Unicode the ranges are from, if they were generated from\n\
+         /// the Unicode Character Database rather than the json.\n\
+         pub const UNICODE_VERSION: Option<&str> = {unicode_version:?};\n"
+    ));
 
     // The `cfg` attribute is not supported on expressions, so we have to
     // generate constants for each feature.
@@ -114,9 +167,45 @@ pub const BASIC_LATIN: RangeInclusive<u32> = 0x00020..=0x0007E; // 0x7F is DEL
             const_name, range.range[0], range.range[1]
         ));
 
-        cargo_toml.push_str(&format!("{feature} = []\n",));
+        if !languages.contains_key(feature) {
+            cargo_toml.push_str(&format!("{feature} = []\n",));
+        }
+    }
+
+    for (feature, list) in code_points {
+        code.push_str(&format!(
+            "/// Code points enabled by the `{feature}` feature, rather than whole blocks.\n"
+        ));
+        code.push_str(&format!("#[cfg(feature = \"{feature}\")]\n"));
+        code.push_str(&format!(
+            "pub const {}_CODE_POINTS: &[RangeInclusive<u32>] = &[\n",
+            feature.to_uppercase().replace('-', "_")
+        ));
+        for range in list {
+            code.push_str(&format!(
+                "    {:#07X}..={:#07X}, // {}\n",
+                range.range[0], range.range[1], range.category
+            ));
+        }
+        code.push_str("];\n");
+    }
+
+    code.push_str(
+        r#"/// Ranges from the json at `LANGSAN_RANGES_JSON` at build time, if it was
+/// set. These are always enabled.
+pub const CUSTOM_RANGES: &[RangeInclusive<u32>] = &[
+"#,
+    );
+
+    for range in &custom {
+        code.push_str(&format!(
+            "    {:#07X}..={:#07X}, // {}\n",
+            range.range[0], range.range[1], range.category
+        ));
     }
 
+    code.push_str("];\n");
+
     code.push_str(
         r#"/// Enabled unicode ranges.
 pub const ENABLED_RANGES: &[RangeInclusive<u32>] = &[
@@ -130,22 +219,647 @@ pub const ENABLED_RANGES: &[RangeInclusive<u32>] = &[
         code.push_str(&format!("    {},\n", const_name));
     }
 
+    for (feature, list) in code_points {
+        for range in list {
+            // Attributes on range expressions are unstable, so parenthesize
+            code.push_str(&format!("    #[cfg(feature = \"{feature}\")]\n"));
+            code.push_str(&format!(
+                "    ({:#07X}..={:#07X}),\n",
+                range.range[0], range.range[1]
+            ));
+        }
+    }
+
+    for range in &custom {
+        code.push_str(&format!(
+            "    {:#07X}..={:#07X},\n",
+            range.range[0], range.range[1]
+        ));
+    }
+
     code.push_str("];\n");
 
-    Ok((code, cargo_toml))
+    code.push_str(
+        r#"/// The [`ENABLED_RANGES`] with their names and features, for [`enabled`].
+pub(crate) const ENABLED: &[EnabledRange] = &[
+    EnabledRange { name: "Whitespace", range: WHITESPACE, feature: None },
+    EnabledRange { name: "Basic Latin", range: BASIC_LATIN, feature: None },
+"#,
+    );
+
+    for ((feature, range), const_name) in features
+        .iter()
+        .zip(ranges.iter())
+        .zip(const_names.iter())
+        .skip(2)
+    {
+        code.push_str(&format!("    #[cfg(feature = \"{feature}\")]\n"));
+        code.push_str(&format!(
+            "    EnabledRange {{ name: {:?}, range: {const_name}, feature: Some({feature:?}) }},\n",
+            range.category
+        ));
+    }
+
+    for (feature, list) in code_points {
+        for range in list {
+            code.push_str(&format!("    #[cfg(feature = \"{feature}\")]\n"));
+            code.push_str(&format!(
+                "    EnabledRange {{ name: {:?}, range: {:#07X}..={:#07X}, feature: Some({feature:?}) }},\n",
+                range.category, range.range[0], range.range[1]
+            ));
+        }
+    }
+
+    for range in &custom {
+        code.push_str(&format!(
+            "    EnabledRange {{ name: {:?}, range: {:#07X}..={:#07X}, feature: None }},\n",
+            range.category, range.range[0], range.range[1]
+        ));
+    }
+
+    code.push_str("];\n");
+
+    code.push_str(
+        r#"/// Every unicode block and its name, enabled or not, in order.
+pub const BLOCKS: &[(RangeInclusive<u32>, &str)] = &[
+"#,
+    );
+
+    for range in ranges {
+        code.push_str(&format!(
+            "    ({:#07X}..={:#07X}, {:?}),\n",
+            range.range[0], range.range[1], range.category
+        ));
+    }
+
+    code.push_str("];\n");
+
+    code.push_str(
+        r#"
+/// The ranges each language feature enables, whether or not it's enabled.
+pub mod languages {
+    use super::*;
+"#,
+    );
+
+    for language in languages.keys() {
+        let mut group = vec!["WHITESPACE,".to_string(), "BASIC_LATIN,".to_string()];
+        language_group(
+            language,
+            languages,
+            code_points,
+            &features,
+            ranges,
+            &mut group,
+        )?;
+        code.push_str(&format!(
+            "\n    /// Ranges enabled by the `{language}` feature.\n    pub const {}: &[RangeInclusive<u32>] = &[\n",
+            language.to_uppercase().replace('-', "_")
+        ));
+        for range in group {
+            code.push_str(&format!("        {range}\n"));
+        }
+        code.push_str("    ];\n");
+    }
+
+    code.push_str(
+        r#"
+    /// Every language feature and the ranges it enables.
+    pub const LANGUAGES: &[(&str, &[RangeInclusive<u32>])] = &[
+"#,
+    );
+
+    for language in languages.keys() {
+        code.push_str(&format!(
+            "        ({language:?}, {}),\n",
+            language.to_uppercase().replace('-', "_")
+        ));
+    }
+
+    code.push_str("    ];\n");
+
+    // And an enum of the enabled ones, to choose between at runtime.
+    code.push_str(
+        r#"
+    /// A compiled-in language feature, for choosing a [`Policy`] at runtime
+    /// with [`Policy::for_languages`]. Only enabled features have a variant.
+    ///
+    /// [`Policy`]: crate::Policy
+    /// [`Policy::for_languages`]: crate::Policy::for_languages
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    #[non_exhaustive]
+    pub enum Lang {
+"#,
+    );
+    for language in languages.keys() {
+        code.push_str(&format!(
+            "        #[cfg(feature = {language:?})]\n        {},\n",
+            variant_name(language)
+        ));
+    }
+    code.push_str(
+        r#"    }
+
+    impl Lang {
+        /// Every enabled language.
+        pub const ALL: &'static [Lang] = &[
+"#,
+    );
+    for language in languages.keys() {
+        code.push_str(&format!(
+            "            #[cfg(feature = {language:?})]\n            Lang::{},\n",
+            variant_name(language)
+        ));
+    }
+    code.push_str(
+        r#"        ];
+
+        /// The name of the language's feature.
+        pub const fn name(self) -> &'static str {
+            match self {
+"#,
+    );
+    for language in languages.keys() {
+        code.push_str(&format!(
+            "                #[cfg(feature = {language:?})]\n                Lang::{} => {language:?},\n",
+            variant_name(language)
+        ));
+    }
+    code.push_str(
+        r#"            }
+        }
+
+        /// The ranges the language's feature enables.
+        pub const fn ranges(self) -> &'static [RangeInclusive<u32>] {
+            match self {
+"#,
+    );
+    for language in languages.keys() {
+        code.push_str(&format!(
+            "                #[cfg(feature = {language:?})]\n                Lang::{} => {},\n",
+            variant_name(language),
+            language.to_uppercase().replace('-', "_")
+        ));
+    }
+    code.push_str("            }\n        }\n    }\n}\n");
+
+    // One test per range, so an off-by-one in the table names its block.
+    code.push_str(
+        r#"
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sanitize_with, Policy};
+
+    /// Check the code points at either end of `range` pass and the ones just
+    /// outside it are removed, unless another enabled range allows them.
+    /// Emoji joiners only pass in context, so they aren't sanitized alone.
+    fn check(range: RangeInclusive<u32>) {
+        let policy = Policy::default();
+        let enabled = |c: u32| ENABLED_RANGES.iter().any(|r| r.contains(&c));
+        let (start, end) = (*range.start(), *range.end());
+        for c in [start, end].into_iter().filter_map(char::from_u32) {
+            assert!(policy.is_allowed(c), "U+{:04X} should be allowed", c as u32);
+            if !crate::emoji::is_joiner(c) {
+                assert_eq!(sanitize_with(&format!("a{}b", c), &policy), None);
+            }
+        }
+        let outside = [start.checked_sub(1), end.checked_add(1)];
+        for c in outside.into_iter().flatten().filter(|&c| !enabled(c)) {
+            let Some(c) = char::from_u32(c) else {
+                continue;
+            };
+            assert!(
+                !policy.is_allowed(c),
+                "U+{:04X} should be removed",
+                c as u32
+            );
+            assert!(sanitize_with(&format!("a{}b", c), &policy).is_some());
+        }
+    }
+
+    /// Check the code points in `ranges` are allowed by the default policy and
+    /// the ones adjacent to each range aren't, unless another enabled range
+    /// allows them. Some are only kept in context, so this doesn't sanitize.
+    #[allow(dead_code)]
+    fn check_code_points(ranges: &[RangeInclusive<u32>]) {
+        let policy = Policy::default();
+        let enabled = |c: u32| ENABLED_RANGES.iter().any(|r| r.contains(&c));
+        for range in ranges {
+            for c in range.clone().filter_map(char::from_u32) {
+                assert!(policy.is_allowed(c), "U+{:04X} should be allowed", c as u32);
+            }
+            let outside = [range.start().checked_sub(1), range.end().checked_add(1)];
+            for c in outside.into_iter().flatten().filter(|&c| !enabled(c)) {
+                if let Some(c) = char::from_u32(c) {
+                    assert!(!policy.is_allowed(c), "U+{:04X} should not be", c as u32);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn enabled_ranges() {
+        let ranges: Vec<_> = enabled().map(|r| r.range.clone()).collect();
+        assert_eq!(ranges, ENABLED_RANGES);
+        assert!(enabled().all(|r| !r.name.is_empty()));
+    }
+
+    #[test]
+    fn whitespace() {
+        check(WHITESPACE);
+    }
+
+    #[test]
+    fn basic_latin() {
+        check(BASIC_LATIN);
+    }
+
+    #[test]
+    fn custom_ranges() {
+        for range in CUSTOM_RANGES {
+            check(range.clone());
+        }
+    }
+"#,
+    );
+
+    for (feature, const_name) in features.iter().zip(const_names.iter()).skip(2) {
+        code.push_str(&format!(
+            "
+    #[test]
+    #[cfg(feature = \"{feature}\")]
+    fn {}() {{
+        check({const_name});
+    }}
+",
+            const_name.to_lowercase()
+        ));
+    }
+
+    // And per list of code points, that its feature enables exactly those.
+    for feature in code_points.keys() {
+        let name = feature.to_uppercase().replace('-', "_");
+        code.push_str(&format!(
+            "
+    #[test]
+    #[cfg(feature = \"{feature}\")]
+    fn code_points_{}() {{
+        check_code_points({name}_CODE_POINTS);
+    }}
+",
+            name.to_lowercase()
+        ));
+    }
+
+    // And per language, that its feature enables its ranges.
+    for language in languages.keys() {
+        let name = language.to_uppercase().replace('-', "_");
+        code.push_str(&format!(
+            "
+    #[test]
+    #[cfg(feature = \"{language}\")]
+    fn language_{}() {{
+        for range in languages::{name} {{
+            assert!(ENABLED_RANGES.contains(range), \"{{:?}}\", range);
+        }}
+    }}
+",
+            name.to_lowercase()
+        ));
+    }
+
+    code.push_str("}\n");
+
+    Ok((code, cargo_toml, features.into_iter().skip(2).collect()))
+}
+
+/// The `Lang` variant for a `language` feature, e.g. `EmojiFull` for
+/// `emoji-full`.
+fn variant_name(language: &str) -> String {
+    language
+        .split('-')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+/// Push `range` onto a language `group`, unless it's already there.
+fn push_range(group: &mut Vec<String>, range: &NamedRange) {
+    let entry = format!(
+        "{:#07X}..={:#07X}, // {}",
+        range.range[0], range.range[1], range.category
+    );
+    if !group.contains(&entry) {
+        group.push(entry);
+    }
+}
+
+/// Push the ranges the `language` feature enables onto `group`, including
+/// those of other languages it enables.
+fn language_group(
+    language: &str,
+    languages: &Languages,
+    code_points: &CodePoints,
+    features: &[String],
+    ranges: &[NamedRange],
+    group: &mut Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // A language may share its name with the block it enables, as `arabic`
+    // does, and may enable code points of its own.
+    if let Some(i) = features.iter().position(|f| f == language) {
+        push_range(group, &ranges[i]);
+    }
+    for range in code_points.get(language).into_iter().flatten() {
+        push_range(group, range);
+    }
+    for dep in &languages[language] {
+        if languages.contains_key(dep) {
+            language_group(dep, languages, code_points, features, ranges, group)?;
+        } else if let Some(i) = features.iter().position(|f| f == dep) {
+            push_range(group, &ranges[i]);
+        } else {
+            return Err(format!("{LANGUAGES_PATH}: unknown feature {dep:?} for {language}").into());
+        }
+    }
+    Ok(())
+}
+
+/// Warn if the language features in Cargo.toml, which can't be generated,
+/// don't match the `languages`.
+fn check_language_features(languages: &Languages) -> Result<(), Box<dyn std::error::Error>> {
+    let cargo_toml = std::fs::read_to_string(PathBuf::from(CRATE_ROOT).join("Cargo.toml"))?;
+    for (language, deps) in languages {
+        let declared = cargo_toml
+            .find(&format!("\n{language} = ["))
+            .map(|start| &cargo_toml[start..])
+            .and_then(|rest| {
+                rest.find(']')
+                    .map(|end| &rest[rest.find('[').unwrap() + 1..end])
+            })
+            .map(|list| {
+                let mut list: Vec<&str> = list
+                    .split(',')
+                    .map(|dep| dep.trim().trim_matches('"'))
+                    .filter(|dep| !dep.is_empty())
+                    .collect();
+                list.sort_unstable();
+                list
+            });
+        let mut expected: Vec<&str> = deps.iter().map(String::as_str).collect();
+        expected.sort_unstable();
+        if declared.as_ref() != Some(&expected) {
+            println!(
+                "cargo:warning=Cargo.toml feature `{language}` should be {expected:?} to match {LANGUAGES_PATH}"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Parse the Unicode Character Database's `Blocks.txt` into ranges named as in
+/// the json, so the features stay the same, and the Unicode version from its
+/// header.
+fn parse_blocks_txt(
+    text: &str,
+) -> Result<(Vec<NamedRange>, Option<String>), Box<dyn std::error::Error>> {
+    let version = text
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("# Blocks-"))
+        .and_then(|line| line.strip_suffix(".txt"))
+        .map(str::to_string);
+    let mut ranges = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parse = || {
+            let (range, name) = line.split_once(';')?;
+            let (start, end) = range.trim().split_once("..")?;
+            let range = [
+                u32::from_str_radix(start, 16).ok()?,
+                u32::from_str_radix(end, 16).ok()?,
+            ];
+            Some((name.trim(), range))
+        };
+        let Some((name, range)) = parse() else {
+            return Err(format!("Blocks.txt:{}: invalid line {line:?}", i + 1).into());
+        };
+        let named = |category: &str, range| NamedRange {
+            category: category.to_string(),
+            range,
+        };
+        match name {
+            // The json splits the control characters off of Basic Latin.
+            "Basic Latin" => {
+                ranges.push(named("Control Character", [range[0], 0x1F]));
+                ranges.push(named(name, [0x20, range[1]]));
+            }
+            "Emoticons" => ranges.push(named("Emoticons (Emoji)", range)),
+            _ => ranges.push(named(name, range)),
+        }
+    }
+    // The first two ranges are replaced by `WHITESPACE` and `BASIC_LATIN`.
+    if ranges.first().map(|r| r.category.as_str()) != Some("Control Character") {
+        return Err("Blocks.txt: the first block must be Basic Latin".into());
+    }
+    Ok((ranges, version))
+}
+
+/// Parse the ranges of each of the [`EMOJI_PROPERTIES`] from `emoji-data.txt`,
+/// sorted and with adjacent ranges merged.
+fn parse_emoji_data(text: &str) -> Result<Vec<Vec<[u32; 2]>>, Box<dyn std::error::Error>> {
+    let mut tables = vec![Vec::new(); EMOJI_PROPERTIES.len()];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parse = || {
+            let (range, property) = line.split_once(';')?;
+            let (start, end) = match range.trim().split_once("..") {
+                Some((start, end)) => (start, end),
+                None => (range.trim(), range.trim()),
+            };
+            let range = [
+                u32::from_str_radix(start, 16).ok()?,
+                u32::from_str_radix(end, 16).ok()?,
+            ];
+            Some((property.trim(), range))
+        };
+        let Some((property, range)) = parse() else {
+            return Err(format!("emoji-data.txt:{}: invalid line {line:?}", i + 1).into());
+        };
+        if let Some(table) = EMOJI_PROPERTIES
+            .iter()
+            .position(|(name, _)| *name == property)
+        {
+            tables[table].push(range);
+        }
+    }
+    for (table, (name, _)) in tables.iter_mut().zip(EMOJI_PROPERTIES) {
+        if table.is_empty() {
+            return Err(format!("emoji-data.txt: no {name} ranges").into());
+        }
+        table.sort_unstable();
+        table.dedup_by(|next, prev| {
+            let adjacent = next[0] <= prev[1] + 1;
+            if adjacent {
+                prev[1] = prev[1].max(next[1]);
+            }
+            adjacent
+        });
+    }
+    Ok(tables)
+}
+
+/// Returns the emoji property tables, for `src/emoji.rs`.
+fn gen_emoji(tables: &[Vec<[u32; 2]>]) -> String {
+    let mut code = r#"// WARNING: This file is generated by build.rs
+// Do not modify this file directly.
+"#
+    .to_string();
+    for (table, (name, const_name)) in tables.iter().zip(EMOJI_PROPERTIES) {
+        code.push_str(&format!(
+            "/// Characters with the `{name}` property, sorted.\n\
+             pub(crate) const {const_name}: &[RangeInclusive<u32>] = &[\n"
+        ));
+        for range in table {
+            code.push_str(&format!("    {:#07X}..={:#07X},\n", range[0], range[1]));
+        }
+        code.push_str("];\n");
+    }
+    code
+}
+
+/// Returns constants describing the build, for `build_info`: the enabled
+/// features and a hash of the range `data`, `emoji` data and `custom` json if
+/// any.
+fn gen_build_info(data: &str, emoji: &str, custom: Option<&str>) -> String {
+    // No feature has an underscore, so the names can be recovered from the
+    // environment.
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    // FNV-1a, which is plenty to tell versions of the data apart.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data
+        .bytes()
+        .chain(emoji.bytes())
+        .chain(custom.unwrap_or_default().bytes())
+    {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    format!(
+        r#"// WARNING: This file is generated by build.rs
+// Do not modify this file directly.
+
+/// The cargo features enabled at build time, sorted.
+pub(crate) const FEATURES: &[&str] = &{features:?};
+/// A hash of the range data the ranges were generated from.
+pub(crate) const DATA_VERSION: &str = "{hash:016x}";
+"#
+    )
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Input json file
-    let json_path = PathBuf::from(CRATE_ROOT).join(JSON_PATH);
+    // Input `Blocks.txt` if one is given or vendored, otherwise the json file,
+    // preferring the submodule if it's checked out
+    println!("cargo:rerun-if-env-changed=LANGSAN_BLOCKS_TXT");
+    let blocks_txt_path = match std::env::var_os("LANGSAN_BLOCKS_TXT") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(PathBuf::from(CRATE_ROOT).join(BLOCKS_TXT_PATH)).filter(|p| p.exists()),
+    };
+    let data_path = match (&blocks_txt_path, PathBuf::from(CRATE_ROOT).join(JSON_PATH)) {
+        (Some(path), _) => path.clone(),
+        (None, path) if path.exists() => path,
+        _ => PathBuf::from(CRATE_ROOT).join(VENDORED_JSON_PATH),
+    };
     // Output `ranges.rs` file
-    let ranges_path = PathBuf::from(CRATE_ROOT).join(RANGES_RS);
-    // Output `Cargo.toml` file (breaks crates.io)
-    // let cargo_toml_path = PathBuf::from(CRATE_ROOT).join("Cargo.toml");
+    let ranges_path = PathBuf::from(std::env::var("OUT_DIR")?).join(RANGES_RS);
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", data_path.display());
+    // In case a `Blocks.txt` is vendored
+    println!("cargo:rerun-if-changed=data");
+
+    // Optional json of ranges to always enable, in the same format
+    println!("cargo:rerun-if-env-changed=LANGSAN_RANGES_JSON");
+    let custom = match std::env::var_os("LANGSAN_RANGES_JSON").map(PathBuf::from) {
+        Some(path) => {
+            println!("cargo:rerun-if-changed={}", path.display());
+            let json = std::fs::read_to_string(&path)
+                .map_err(|e| format!("LANGSAN_RANGES_JSON={}: {}", path.display(), e))?;
+            Some(json)
+        }
+        None => None,
+    };
 
-    let json = std::fs::read_to_string(json_path)?;
-    let (ranges_rs, _cargo_toml) = gen_ranges(&json)?;
+    let languages_path = PathBuf::from(CRATE_ROOT).join(LANGUAGES_PATH);
+    println!("cargo:rerun-if-changed={}", languages_path.display());
+    let languages: Languages = serde_json::from_str(&std::fs::read_to_string(languages_path)?)?;
+    check_language_features(&languages)?;
+
+    let code_points_path = PathBuf::from(CRATE_ROOT).join(CODE_POINTS_PATH);
+    println!("cargo:rerun-if-changed={}", code_points_path.display());
+    let code_points: CodePoints =
+        serde_json::from_str(&std::fs::read_to_string(code_points_path)?)?;
+
+    let data = std::fs::read_to_string(&data_path)
+        .map_err(|e| format!("{}: {}", data_path.display(), e))?;
+    // Input `emoji-data.txt` if one is given, otherwise the vendored one
+    println!("cargo:rerun-if-env-changed=LANGSAN_EMOJI_DATA");
+    let emoji_data_path = match std::env::var_os("LANGSAN_EMOJI_DATA") {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(CRATE_ROOT).join(EMOJI_DATA_PATH),
+    };
+    println!("cargo:rerun-if-changed={}", emoji_data_path.display());
+    let emoji_data = std::fs::read_to_string(&emoji_data_path)
+        .map_err(|e| format!("{}: {}", emoji_data_path.display(), e))?;
+    let emoji_path = PathBuf::from(std::env::var("OUT_DIR")?).join(EMOJI_RS);
+    std::fs::write(emoji_path, gen_emoji(&parse_emoji_data(&emoji_data)?))?;
+
+    let (ranges, unicode_version) = match blocks_txt_path {
+        Some(_) => parse_blocks_txt(&data)?,
+        None => (serde_json::from_str(&data)?, None),
+    };
+    let (ranges_rs, _cargo_toml, features) = gen_ranges(
+        &ranges,
+        unicode_version.as_deref(),
+        custom.as_deref(),
+        &languages,
+        &code_points,
+    )?;
     std::fs::write(ranges_path, ranges_rs)?;
-    // std::fs::write(cargo_toml_path, cargo_toml)?;
+    let build_info_path = PathBuf::from(std::env::var("OUT_DIR")?).join(BUILD_INFO_RS);
+    std::fs::write(
+        build_info_path,
+        gen_build_info(&data, &emoji_data, custom.as_deref()),
+    )?;
+
+    // The generated code has a feature per block of the data it was built
+    // from, and a vendored `Blocks.txt` can have blocks newer than the
+    // features in Cargo.toml, so tell rustc about all of them to avoid
+    // `unexpected_cfgs` warnings.
+    let values: Vec<String> = features.iter().map(|f| format!("\"{f}\"")).collect();
+    println!(
+        "cargo:rustc-check-cfg=cfg(feature, values({}))",
+        values.join(", ")
+    );
     Ok(())
 }
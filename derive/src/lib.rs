@@ -0,0 +1,152 @@
+//! `#[derive(Sanitize)]` for [`langsan`], enabled by its `derive` feature.
+//!
+//! [`langsan`]: https://docs.rs/langsan
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Field, Fields, GenericParam, Index, LitStr,
+    Member,
+};
+
+/// Derive `langsan::Sanitize` for a struct, sanitizing every field in place.
+/// Each field's type must implement `Sanitize` too, unless it's skipped.
+///
+/// Fields may be annotated with `#[langsan(...)]`:
+///
+/// * `policy = "name"` sanitizes the field with the policy registered under
+///   `name` with `langsan::register_policy`, rather than the active one, since
+///   a struct often mixes code, URLs and natural language. `try_sanitize` and
+///   `sanitize_strict` fail if no policy is registered under `name`.
+/// * `skip` leaves the field alone, such as for numbers and ids.
+///
+/// ```ignore
+/// use langsan::{register_policy, Policy, Sanitize};
+///
+/// #[derive(Sanitize)]
+/// struct Snippet {
+///     #[langsan(policy = "code")]
+///     code: String,
+///     #[langsan(policy = "chat_input")]
+///     comment: Option<String>,
+///     #[langsan(skip)]
+///     line: u32,
+/// }
+///
+/// register_policy("code", Policy::default());
+/// register_policy("chat_input", Policy::default().forbid(['<', '>']));
+/// ```
+///
+/// # Panics
+///
+/// The derived `sanitize` panics if a field's named policy isn't registered,
+/// since it can't return an error. Call `try_sanitize` instead where a policy
+/// might not be, such as when names come from configuration.
+#[proc_macro_derive(Sanitize, attributes(langsan))]
+pub fn derive_sanitize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a field is sanitized, from its `#[langsan(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    policy: Option<LitStr>,
+    skip: bool,
+}
+
+impl Options {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut options = Options::default();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("langsan")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("policy") {
+                    options.policy = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `policy = \"name\"` or `skip`"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Sanitize` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut sanitize = Vec::new();
+    let mut try_sanitize = Vec::new();
+    let mut strict = Vec::new();
+    for (i, field) in fields.into_iter().enumerate() {
+        let options = Options::parse(field)?;
+        if options.skip {
+            continue;
+        }
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        };
+        match options.policy {
+            Some(name) => {
+                sanitize.push(quote! {
+                    ::langsan::__sanitize_named(#name, &mut self.#member)
+                });
+                try_sanitize.push(quote! {
+                    ::langsan::__try_sanitize_named(#name, &mut self.#member)?
+                });
+                strict.push(quote! {
+                    ::langsan::__sanitize_strict_named(#name, &self.#member)?;
+                });
+            }
+            None => {
+                sanitize.push(quote! { ::langsan::Sanitize::sanitize(&mut self.#member) });
+                try_sanitize.push(quote! {
+                    ::langsan::Sanitize::try_sanitize(&mut self.#member)?
+                });
+                strict.push(quote! { ::langsan::Sanitize::sanitize_strict(&self.#member)?; });
+            }
+        }
+    }
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::langsan::Sanitize));
+        }
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::langsan::Sanitize for #ident #ty_generics #where_clause {
+            fn sanitize(&mut self) -> usize {
+                0 #(+ #sanitize)*
+            }
+
+            fn sanitize_strict(&self) -> ::std::result::Result<(), ::langsan::Error> {
+                #(#strict)*
+                ::std::result::Result::Ok(())
+            }
+
+            fn try_sanitize(&mut self) -> ::std::result::Result<usize, ::langsan::Error> {
+                ::std::result::Result::Ok(0 #(+ #try_sanitize)*)
+            }
+        }
+    })
+}
@@ -0,0 +1,12 @@
+//! The output of `sanitize` is always clean, so sanitizing again changes
+//! nothing.
+#![no_main]
+
+use langsan::{sanitize, sanitize_outcome, sanitize_strict};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    let output = sanitize(s).unwrap_or_else(|| s.to_string());
+    assert!(sanitize_outcome(&output).is_clean());
+    assert!(sanitize_strict(&output).is_ok());
+});
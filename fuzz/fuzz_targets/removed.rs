@@ -0,0 +1,23 @@
+//! Exactly the reported span is removed, and nothing else. In verbose mode it
+//! is replaced with a marker, so running this with and without the `verbose`
+//! feature checks that both modes remove the same bytes.
+#![no_main]
+
+use langsan::sanitize_outcome;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    let outcome = sanitize_outcome(s);
+    let Some(report) = outcome.report() else {
+        return;
+    };
+    let span = report.removed().expect("the default policy only removes");
+    assert_eq!(report.removed_bytes(), span.len());
+    assert_eq!(report.replaced(), 0);
+    let marker = match cfg!(feature = "verbose") {
+        true => format!("[{} BYTES SANITIZED]", span.len()),
+        false => String::new(),
+    };
+    let expected = [&s[..span.start], &marker, &s[span.end..]].concat();
+    assert_eq!(outcome.as_str(), expected);
+});
@@ -0,0 +1,18 @@
+//! Nothing panics on arbitrary UTF-8, including lossily decoded bytes.
+#![no_main]
+
+use langsan::{sanitize, sanitize_outcome, sanitize_strict, CowStr, Sanitize};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let s = String::from_utf8_lossy(data);
+    let outcome = sanitize_outcome(&s);
+    assert_eq!(outcome.is_clean(), sanitize(&s).is_none());
+    assert_eq!(outcome.is_clean(), sanitize_strict(&s).is_ok());
+    assert_eq!(CowStr::from(s.as_ref()).as_ref(), outcome.as_str());
+
+    let mut owned = s.to_string();
+    let modified = owned.sanitize() > 0;
+    assert_eq!(modified, !outcome.is_clean());
+    assert_eq!(owned, outcome.as_str());
+});
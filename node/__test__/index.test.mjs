@@ -0,0 +1,38 @@
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+import test from "node:test";
+
+const { Policy, sanitize, sanitizeOutcome, sanitizeStrict } = createRequire(
+  import.meta.url,
+)("../index.js");
+
+test("sanitize", () => {
+  assert.equal(sanitize("hello"), null);
+  assert.equal(sanitize("hi 🏴 there 🏴!"), "hi !");
+  assert.equal(sanitizeStrict("hello"), "hello");
+  assert.throws(() => sanitizeStrict("hi 🏴"), /U\+1F3F4 at byte 3/);
+});
+
+test("sanitizeOutcome", () => {
+  assert.deepEqual(sanitizeOutcome("hello"), { output: "hello", clean: true });
+  assert.deepEqual(sanitizeOutcome("hi 🏴 there 🏴!"), {
+    output: "hi !",
+    clean: false,
+    report: { removedStart: 3, removedEnd: 18, removedBytes: 15, replaced: 0 },
+  });
+});
+
+test("Policy", () => {
+  const policy = new Policy([{ start: 0x20, end: 0x7e }]);
+  policy.forbid("<>");
+  assert.ok(policy.isAllowed("a"));
+  assert.ok(!policy.isAllowed("<"));
+  assert.equal(policy.sanitize("a<b>c"), "ac");
+  assert.throws(() => policy.sanitizeStrict("\n"), /U\+000A/);
+
+  const either = policy.union(Policy.deny("x"));
+  assert.ok(either.isAllowed("\n"));
+  assert.ok(!policy.intersect(Policy.deny("a")).isAllowed("a"));
+  assert.ok(new Policy().isAllowed("\n"));
+  assert.throws(() => new Policy([{ start: 2, end: 1 }]), /invalid policy/);
+});
@@ -0,0 +1,165 @@
+//! Node.js bindings for [`langsan`], mirroring its policy and report API.
+#![deny(clippy::all)]
+
+use napi::{Error, Result};
+use napi_derive::napi;
+
+/// An inclusive range of Unicode code points.
+#[napi(object)]
+pub struct Range {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// How sanitization modified a string.
+#[napi(object)]
+pub struct Report {
+    /// Byte offset of the start of the removed span, if any.
+    pub removed_start: Option<u32>,
+    /// Byte offset just past the end of the removed span, if any.
+    pub removed_end: Option<u32>,
+    /// The number of bytes removed.
+    pub removed_bytes: u32,
+    /// The number of characters replaced outside the removed span.
+    pub replaced: u32,
+}
+
+impl From<&langsan::Report> for Report {
+    fn from(report: &langsan::Report) -> Self {
+        let removed = report.removed();
+        Self {
+            removed_start: removed.as_ref().map(|r| r.start as u32),
+            removed_end: removed.as_ref().map(|r| r.end as u32),
+            removed_bytes: report.removed_bytes() as u32,
+            replaced: report.replaced() as u32,
+        }
+    }
+}
+
+/// The result of `sanitizeOutcome`.
+#[napi(object)]
+pub struct Outcome {
+    /// The sanitized string.
+    pub output: String,
+    /// True if the input was not modified.
+    pub clean: bool,
+    /// How the input was modified, if it was.
+    pub report: Option<Report>,
+}
+
+impl From<langsan::Outcome<'_>> for Outcome {
+    fn from(outcome: langsan::Outcome<'_>) -> Self {
+        Self {
+            clean: outcome.is_clean(),
+            report: outcome.report().map(Report::from),
+            output: outcome.into_cow().into_owned(),
+        }
+    }
+}
+
+fn to_error(e: langsan::Error) -> Error {
+    Error::from_reason(e.to_string())
+}
+
+/// A sanitization policy.
+#[napi]
+pub struct Policy(langsan::Policy);
+
+#[napi]
+impl Policy {
+    /// Create a policy allowing only `ranges`, or the default policy if
+    /// omitted. Known-problematic characters are still forbidden.
+    #[napi(constructor)]
+    pub fn new(ranges: Option<Vec<Range>>) -> Result<Self> {
+        let Some(ranges) = ranges else {
+            return Ok(Self(langsan::Policy::default()));
+        };
+        if let Some(r) = ranges
+            .iter()
+            .find(|r| r.start > r.end || r.end > char::MAX as u32)
+        {
+            return Err(to_error(langsan::Error::InvalidPolicy(format!(
+                "invalid range [{}, {}]",
+                r.start, r.end
+            ))));
+        }
+        let ranges: Vec<_> = ranges.iter().map(|r| r.start..=r.end).collect();
+        Ok(Self(langsan::Policy::new(ranges)))
+    }
+
+    /// Create a policy allowing everything except the characters in `chars`.
+    #[napi(factory)]
+    pub fn deny(chars: String) -> Self {
+        Self(langsan::Policy::deny(chars.chars()))
+    }
+
+    /// Additionally forbid the characters in `chars`, even if they are
+    /// otherwise allowed.
+    #[napi]
+    pub fn forbid(&mut self, chars: String) {
+        let policy = std::mem::take(&mut self.0);
+        self.0 = policy.forbid(chars.chars());
+    }
+
+    /// A policy allowing characters allowed by either `this` or `other`.
+    #[napi]
+    pub fn union(&self, other: &Policy) -> Policy {
+        Policy(self.0.clone().union(other.0.clone()))
+    }
+
+    /// A policy allowing only characters allowed by both `this` and `other`.
+    #[napi]
+    pub fn intersect(&self, other: &Policy) -> Policy {
+        Policy(self.0.clone().intersect(other.0.clone()))
+    }
+
+    /// A policy applying `this` and then `other` to the result.
+    #[napi]
+    pub fn chain(&self, other: &Policy) -> Policy {
+        Policy(self.0.clone().chain(other.0.clone()))
+    }
+
+    /// Returns true if the first character of `c` survives sanitization
+    /// unchanged.
+    #[napi]
+    pub fn is_allowed(&self, c: String) -> bool {
+        c.chars().next().is_some_and(|c| self.0.is_allowed(c))
+    }
+
+    /// Return the sanitized `input`, or null if it was not modified.
+    #[napi]
+    pub fn sanitize(&self, input: String) -> Option<String> {
+        langsan::sanitize_with(&input, &self.0)
+    }
+
+    /// Sanitize `input`, describing whether and how it was modified.
+    #[napi]
+    pub fn sanitize_outcome(&self, input: String) -> Outcome {
+        langsan::sanitize_outcome_with(&input, &self.0).into()
+    }
+
+    /// Return `input` unchanged, or throw if sanitization would modify it.
+    #[napi]
+    pub fn sanitize_strict(&self, input: String) -> Result<String> {
+        langsan::with_policy(self.0.clone(), || sanitize_strict(input))
+    }
+}
+
+/// Return the sanitized `input`, or null if it was not modified.
+#[napi]
+pub fn sanitize(input: String) -> Option<String> {
+    langsan::sanitize(&input)
+}
+
+/// Sanitize `input`, describing whether and how it was modified.
+#[napi]
+pub fn sanitize_outcome(input: String) -> Outcome {
+    langsan::sanitize_outcome(&input).into()
+}
+
+/// Return `input` unchanged, or throw if sanitization would modify it.
+#[napi]
+pub fn sanitize_strict(input: String) -> Result<String> {
+    langsan::sanitize_strict(&input).map_err(to_error)?;
+    Ok(input)
+}
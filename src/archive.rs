@@ -0,0 +1,136 @@
+/// Zero-copy archiving with [`rkyv`].
+use std::ops::Deref;
+
+use rkyv::{
+    bytecheck::{CheckBytes, Verify},
+    rancor::{Fallible, Source},
+    string::{ArchivedString, StringResolver},
+    Archive, Deserialize, Place, Portable, Serialize, SerializeUnsized,
+};
+
+use crate::{san::sanitize_strict, CowStr};
+
+/// An archived [`CowStr`]. Validation (e.g. [`rkyv::access`]) re-checks that
+/// the string is clean under the active policy, so a tampered or stale archive
+/// can't smuggle content past the type.
+#[derive(Debug, PartialEq, Eq, Hash, Portable, CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck, verify)]
+#[rkyv(crate = rkyv)]
+#[repr(transparent)]
+pub struct ArchivedCowStr {
+    inner: ArchivedString,
+}
+
+impl ArchivedCowStr {
+    /// The archived string.
+    pub fn as_str(&self) -> &str {
+        self.inner.as_str()
+    }
+}
+
+unsafe impl<C> Verify<C> for ArchivedCowStr
+where
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        // The inner string has already been checked to be valid UTF-8.
+        match sanitize_strict(self.as_str()) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(C::Error::new(err)),
+        }
+    }
+}
+
+impl Archive for CowStr<'_> {
+    type Archived = ArchivedCowStr;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `ArchivedCowStr` is a `repr(transparent)` `ArchivedString`.
+        let out = unsafe { out.cast_unchecked::<ArchivedString>() };
+        ArchivedString::resolve_from_str(self.as_ref(), resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for CowStr<'_>
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_ref(), serializer)
+    }
+}
+
+impl<D> Deserialize<CowStr<'static>, D> for ArchivedCowStr
+where
+    D: Fallible + ?Sized,
+{
+    /// Deserialization sanitizes again, in case the archive was accessed
+    /// without validation.
+    fn deserialize(&self, _: &mut D) -> Result<CowStr<'static>, D::Error> {
+        Ok(CowStr::from(self.as_str().to_owned()))
+    }
+}
+
+impl Deref for ArchivedCowStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ArchivedCowStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for ArchivedCowStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn test_rkyv() {
+        #[derive(Archive, Serialize, Deserialize)]
+        #[rkyv(crate = rkyv)]
+        struct Transcript {
+            messages: Vec<CowStr<'static>>,
+        }
+
+        let transcript = Transcript {
+            messages: vec![
+                "Hello".into(),
+                "A longer message, stored out of line!".into(),
+            ],
+        };
+        let bytes = rkyv::to_bytes::<Error>(&transcript).unwrap();
+        let archived = rkyv::access::<ArchivedTranscript, Error>(&bytes).unwrap();
+        assert_eq!(archived.messages[0].as_str(), "Hello");
+        assert_eq!(
+            &*archived.messages[1],
+            "A longer message, stored out of line!"
+        );
+        let transcript: Transcript = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(
+            transcript.messages[1].as_ref(),
+            "A longer message, stored out of line!"
+        );
+
+        // A tampered archive fails validation
+        let mut bytes = rkyv::to_bytes::<Error>(&CowStr::from("Hello, world!")).unwrap();
+        let at = bytes.iter().position(|b| *b == b'!').unwrap();
+        bytes[at] = 0x7F; // DEL
+        assert!(rkyv::access::<ArchivedCowStr, Error>(&bytes).is_err());
+    }
+}
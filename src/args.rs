@@ -0,0 +1,115 @@
+/// [`clap`] value parsing for sanitized command-line arguments.
+use std::ffi::OsStr;
+
+use clap::{
+    builder::{TypedValueParser, ValueParserFactory},
+    error::ErrorKind,
+    Arg, Command,
+};
+
+use crate::{CowStr, SanitizedString};
+
+/// A [`TypedValueParser`] producing [`SanitizedString`]s. This is what
+/// `value_parser!(SanitizedString)` uses.
+///
+/// ```
+/// use clap::{value_parser, Arg, Command};
+/// use langsan::{SanitizedParser, SanitizedString};
+///
+/// let matches = Command::new("chat")
+///     .arg(Arg::new("prompt").value_parser(value_parser!(SanitizedString)))
+///     .arg(Arg::new("system").long("system").value_parser(SanitizedParser::strict()))
+///     .get_matches_from(["chat", "hello", "--system", "be nice"]);
+/// assert_eq!(&**matches.get_one::<SanitizedString>("prompt").unwrap(), "hello");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SanitizedParser {
+    strict: bool,
+}
+
+impl SanitizedParser {
+    /// Sanitize arguments with the active policy.
+    pub fn new() -> Self {
+        Self { strict: false }
+    }
+
+    /// Reject arguments that would be modified by sanitization instead.
+    pub fn strict() -> Self {
+        Self { strict: true }
+    }
+}
+
+impl TypedValueParser for SanitizedParser {
+    type Value = SanitizedString;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        if !self.strict {
+            return Ok(CowStr::from(value).into_static());
+        }
+        CowStr::try_new(value)
+            .map(CowStr::into_static)
+            .map_err(|e| {
+                let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+                clap::Error::raw(
+                    ErrorKind::ValueValidation,
+                    format!("invalid value for '{}': {}\n", arg, e),
+                )
+                .with_cmd(cmd)
+            })
+    }
+}
+
+impl ValueParserFactory for CowStr<'static> {
+    type Parser = SanitizedParser;
+
+    fn value_parser() -> Self::Parser {
+        SanitizedParser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::value_parser;
+
+    use super::*;
+
+    fn command(parser: SanitizedParser) -> Command {
+        Command::new("test").arg(Arg::new("prompt").value_parser(parser))
+    }
+
+    #[test]
+    fn test_sanitized_parser() {
+        let matches = Command::new("test")
+            .arg(Arg::new("prompt").value_parser(value_parser!(SanitizedString)))
+            .try_get_matches_from(["test", "hi🏴"])
+            .unwrap();
+        let prompt = matches.get_one::<SanitizedString>("prompt").unwrap();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(&**prompt, "hi");
+        assert!(prompt.starts_with("hi"));
+
+        let matches = command(SanitizedParser::strict())
+            .try_get_matches_from(["test", "hi"])
+            .unwrap();
+        assert_eq!(
+            &**matches.get_one::<SanitizedString>("prompt").unwrap(),
+            "hi"
+        );
+
+        let err = command(SanitizedParser::strict())
+            .try_get_matches_from(["test", "hi🏴"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err
+            .to_string()
+            .contains("invalid value for '[prompt]': disallowed character U+1F3F4 at byte 2"));
+    }
+}
@@ -0,0 +1,222 @@
+/// Batch sanitization of collections.
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use crate::{
+    cow::push_sanitized,
+    policy::{with_current, CharPolicy},
+    san::{sanitize_in_place, scan},
+    CowStr,
+};
+
+/// Sanitize every string in `strs` with the active policy. Clean strings are
+/// borrowed, so only modified ones are copied.
+pub fn sanitize_all<'a, I>(strs: I) -> Vec<CowStr<'a>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    with_current(|policy| {
+        strs.into_iter()
+            .map(|s| {
+                let mut output = String::new();
+                let mut edits = Vec::new();
+                match push_sanitized(s, policy, &mut output, &mut edits) {
+                    Some(_) => CowStr {
+                        inner: output.into(),
+                        edits,
+                    },
+                    None => CowStr::clean(s.into()),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Sanitize every string in `strs` in place with the active policy. A single
+/// scratch buffer is reused across items, so this is suited to cleaning large
+/// datasets. Returns the number of strings modified.
+pub fn sanitize_all_in_place(strs: &mut [String]) -> usize {
+    with_current(|policy| {
+        let mut scratch = String::new();
+        let mut modified = 0;
+        for s in strs.iter_mut() {
+            modified += sanitize_in_place(s, policy, &mut scratch) as usize;
+        }
+        modified
+    })
+}
+
+/// Sanitize the keys and values of `map` in place with the active policy.
+/// Entries whose keys change are reinserted, unless the sanitized key is
+/// already present, in which case they are dropped so a sanitized key can never
+/// shadow a clean one. If several modified keys sanitize to the same key, the
+/// entry with the least original key, in byte order, is kept, whatever the
+/// order of the map. Returns the number of keys and values modified.
+pub fn sanitize_hash_map<S>(map: &mut HashMap<String, String, S>) -> usize
+where
+    S: BuildHasher,
+{
+    with_current(|policy| {
+        let mut scratch = String::new();
+        let mut modified = 0;
+        for value in map.values_mut() {
+            modified += sanitize_in_place(value, policy, &mut scratch) as usize;
+        }
+        modified + sanitize_keys(map, policy, &mut scratch)
+    })
+}
+
+/// [`sanitize_hash_map`] for a [`BTreeMap`].
+pub fn sanitize_btree_map(map: &mut BTreeMap<String, String>) -> usize {
+    with_current(|policy| {
+        let mut scratch = String::new();
+        let mut modified = 0;
+        for value in map.values_mut() {
+            modified += sanitize_in_place(value, policy, &mut scratch) as usize;
+        }
+        modified + sanitize_keys(map, policy, &mut scratch)
+    })
+}
+
+/// A map with `String` keys, for [`sanitize_keys`].
+pub(crate) trait StringKeys {
+    type Value;
+
+    fn string_keys(&self) -> impl Iterator<Item = &String>;
+
+    fn remove_key(&mut self, key: &str) -> Option<Self::Value>;
+
+    /// Insert `value` at `key` unless `key` is already present.
+    fn insert_vacant(&mut self, key: String, value: Self::Value);
+}
+
+impl<V, S: BuildHasher> StringKeys for HashMap<String, V, S> {
+    type Value = V;
+
+    fn string_keys(&self) -> impl Iterator<Item = &String> {
+        self.keys()
+    }
+
+    fn remove_key(&mut self, key: &str) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn insert_vacant(&mut self, key: String, value: V) {
+        self.entry(key).or_insert(value);
+    }
+}
+
+impl<V> StringKeys for BTreeMap<String, V> {
+    type Value = V;
+
+    fn string_keys(&self) -> impl Iterator<Item = &String> {
+        self.keys()
+    }
+
+    fn remove_key(&mut self, key: &str) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn insert_vacant(&mut self, key: String, value: V) {
+        self.entry(key).or_insert(value);
+    }
+}
+
+/// Sanitize the keys of `map` with `policy`, reinserting the entries whose
+/// keys change unless the sanitized key is already present, as documented for
+/// [`sanitize_hash_map`]. Returns the number of keys modified.
+pub(crate) fn sanitize_keys<M, P>(map: &mut M, policy: &P, scratch: &mut String) -> usize
+where
+    M: StringKeys,
+    P: CharPolicy + ?Sized,
+{
+    let mut dirty: Vec<String> = map
+        .string_keys()
+        .filter(|key| scan(key, policy).is_some())
+        .cloned()
+        .collect();
+    // So which of several keys sanitizing to the same one is kept doesn't
+    // depend on the order of a `HashMap`
+    dirty.sort_unstable();
+    let mut modified = 0;
+    for mut key in dirty {
+        if let Some(value) = map.remove_key(&key) {
+            sanitize_in_place(&mut key, policy, scratch);
+            map.insert_vacant(key, value);
+            modified += 1;
+        }
+    }
+    modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_all() {
+        let strs = ["Hello", "world🏴!", ""];
+        let sanitized = sanitize_all(strs);
+        assert_eq!(sanitized.len(), 3);
+        assert!(sanitized[0].is_borrowed());
+        assert!(sanitized[1].is_owned());
+        assert!(sanitized[2].is_borrowed());
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(sanitized[1].as_ref(), "world!");
+
+        let mut strs = vec!["🏴Hello".to_string(), "world".into(), "🏴".into()];
+        assert_eq!(sanitize_all_in_place(&mut strs), 2);
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(strs, ["Hello", "world", ""]);
+        #[cfg(feature = "verbose")]
+        assert_eq!(
+            strs,
+            ["[4 BYTES SANITIZED]Hello", "world", "[4 BYTES SANITIZED]"]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_sanitize_maps() {
+        let entries = [
+            ("Authorization", "Bearer abc"),
+            ("Authorization🏴", "Bearer evil"),
+            ("X-Custom🏴", "value🏴"),
+            ("Accept", "*/*"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+
+        let mut map: HashMap<_, _> = entries.clone().into_iter().collect();
+        assert_eq!(sanitize_hash_map(&mut map), 3);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["Authorization"], "Bearer abc");
+        assert_eq!(map["X-Custom"], "value");
+        assert_eq!(sanitize_hash_map(&mut map), 0);
+
+        // The least modified key is kept, whatever the hasher's order
+        for _ in 0..8 {
+            let mut map: HashMap<_, _> = [("a🏴", "1"), ("🏴a", "2"), ("a\u{202E}", "3")]
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .into_iter()
+                .collect();
+            assert_eq!(sanitize_hash_map(&mut map), 3);
+            assert_eq!(
+                map.into_iter().collect::<Vec<_>>(),
+                [("a".into(), "3".into())]
+            );
+        }
+
+        let mut map: BTreeMap<_, _> = entries.into_iter().collect();
+        assert_eq!(sanitize_btree_map(&mut map), 3);
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            [
+                ("Accept".to_string(), "*/*".to_string()),
+                ("Authorization".into(), "Bearer abc".into()),
+                ("X-Custom".into(), "value".into()),
+            ]
+        );
+    }
+}
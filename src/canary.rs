@@ -0,0 +1,154 @@
+/// Canary tokens for detecting system prompt leaks.
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Prefix of every canary, so leaks are recognizable in logs.
+const PREFIX: &str = "canary-";
+
+/// A random string to embed in a system prompt. If it shows up in model
+/// output, the prompt has leaked.
+///
+/// ```
+/// use langsan::{Canary, Leak};
+///
+/// let canary = Canary::new();
+/// let prompt = canary.embed("You are a helpful assistant.");
+/// assert!(prompt.contains(canary.as_str()));
+///
+/// assert_eq!(canary.scan("Hello!"), None);
+/// let output = format!("My instructions end with {}", canary.as_str().to_uppercase());
+/// assert!(matches!(canary.scan(&output), Some(Leak::NearMiss(_))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Canary {
+    token: String,
+}
+
+/// Where a [`Canary`] was found in model output, as a byte range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Leak {
+    /// The canary appears verbatim.
+    Exact(Range<usize>),
+    /// The canary appears with its case or punctuation changed, with
+    /// characters inserted between its letters, or in part.
+    NearMiss(Range<usize>),
+}
+
+impl Canary {
+    /// Generate a new random canary. This is not cryptographically secure, but
+    /// is unpredictable enough that a model can't produce it by chance.
+    pub fn new() -> Self {
+        Self {
+            token: format!("{PREFIX}{:016x}", random()),
+        }
+    }
+
+    /// The canary string.
+    pub fn as_str(&self) -> &str {
+        &self.token
+    }
+
+    /// Append the canary to `system_prompt` on a line of its own.
+    pub fn embed(&self, system_prompt: &str) -> String {
+        format!("{system_prompt}\n\n{}", self.token)
+    }
+
+    /// Scan model `output` for the canary. An exact match is preferred to a
+    /// near miss, which is found by comparing only ASCII letters and digits,
+    /// ignoring case, so `CANARY-ABC…`, `c a n a r y…` and `canary-\u{200B}abc…`
+    /// all match. Either half of the random part alone is also a near miss.
+    pub fn scan(&self, output: &str) -> Option<Leak> {
+        if let Some(start) = output.find(&self.token) {
+            return Some(Leak::Exact(start..start + self.token.len()));
+        }
+
+        // The letters and digits of `output`, lowercased, with their offsets.
+        let (normalized, offsets): (String, Vec<usize>) = output
+            .char_indices()
+            .filter(|(_, c)| c.is_ascii_alphanumeric())
+            .map(|(i, c)| (c.to_ascii_lowercase(), i))
+            .unzip();
+        let random = &self.token[PREFIX.len()..];
+        let (first, second) = random.split_at(random.len() / 2);
+        let whole = self.token.replace('-', "");
+        for needle in [whole.as_str(), first, second] {
+            if let Some(start) = normalized.find(needle) {
+                let end = offsets[start + needle.len() - 1] + 1;
+                return Some(Leak::NearMiss(offsets[start]..end));
+            }
+        }
+        None
+    }
+}
+
+impl Default for Canary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Canary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.token)
+    }
+}
+
+/// A random number from the standard library's randomly keyed hasher, mixed
+/// with a counter and the time so successive calls differ.
+fn random() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    let now = SystemTime::now().duration_since(UNIX_EPOCH);
+    hasher.write_u128(now.map_or(0, |d| d.as_nanos()));
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canary() {
+        let canary = Canary::new();
+        assert_ne!(canary, Canary::new());
+        assert_eq!(canary.as_str().len(), PREFIX.len() + 16);
+        assert_eq!(canary.to_string(), canary.as_str());
+        assert!(canary.embed("Be nice.").starts_with("Be nice.\n"));
+    }
+
+    #[test]
+    fn test_scan() {
+        let canary = Canary {
+            token: "canary-0123456789abcdef".into(),
+        };
+        assert_eq!(canary.scan("nothing to see"), None);
+        assert_eq!(canary.scan("canary-0123"), None);
+        assert_eq!(
+            canary.scan("x canary-0123456789abcdef"),
+            Some(Leak::Exact(2..25))
+        );
+        assert_eq!(
+            canary.scan("x CANARY-0123456789ABCDEF"),
+            Some(Leak::NearMiss(2..25))
+        );
+        assert_eq!(
+            canary.scan("c a n a r y 0123\u{200B}4567 89ab cdef!"),
+            Some(Leak::NearMiss(0..33))
+        );
+        assert_eq!(
+            canary.scan("the key is 01234567"),
+            Some(Leak::NearMiss(11..19))
+        );
+        assert_eq!(
+            canary.scan("the key is 89-AB-CD-EF"),
+            Some(Leak::NearMiss(11..22))
+        );
+    }
+}
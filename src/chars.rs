@@ -0,0 +1,135 @@
+/// Sanitization of character iterators.
+use crate::{
+    policy::{classify_guarded, with_current, CharPolicy, Decision},
+    Policy,
+};
+
+/// An adaptor over an iterator of characters that removes and replaces them
+/// per a policy, for char-level parsers and decoders that never build a
+/// string.
+///
+/// Unlike [`sanitize`], each character is decided on by itself, since the
+/// characters after it aren't known yet. Nothing between removed characters
+/// is removed, and with the `emoji-full` feature, emoji joiners are kept or
+/// removed by the policy alone.
+///
+/// ```
+/// use langsan::SanitizeChars;
+///
+/// let chars = SanitizeChars::new("a🏴b🏴c".chars());
+/// assert_eq!(chars.collect::<String>(), "abc");
+///
+/// let tabs = |c: char| match c {
+///     '\t' => langsan::Decision::Replace(' '),
+///     _ => langsan::Decision::Allow,
+/// };
+/// let chars = SanitizeChars::with_policy("a\tb".chars(), tabs);
+/// assert_eq!(chars.collect::<String>(), "a b");
+/// ```
+///
+/// [`sanitize`]: crate::sanitize
+#[derive(Debug, Clone)]
+pub struct SanitizeChars<I, P = Policy> {
+    chars: I,
+    policy: P,
+    removed: usize,
+    replaced: usize,
+}
+
+impl<I> SanitizeChars<I>
+where
+    I: Iterator<Item = char>,
+{
+    /// Sanitize `chars` with a copy of the active policy.
+    pub fn new(chars: I) -> Self {
+        Self::with_policy(chars, with_current(Policy::clone))
+    }
+}
+
+impl<I, P> SanitizeChars<I, P>
+where
+    I: Iterator<Item = char>,
+    P: CharPolicy,
+{
+    /// Sanitize `chars` with `policy`.
+    pub fn with_policy(chars: I, policy: P) -> Self {
+        Self {
+            chars,
+            policy,
+            removed: 0,
+            replaced: 0,
+        }
+    }
+
+    /// The number of characters removed so far.
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// The number of characters replaced so far.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// The underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.chars
+    }
+}
+
+impl<I, P> Iterator for SanitizeChars<I, P>
+where
+    I: Iterator<Item = char>,
+    P: CharPolicy,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        for c in self.chars.by_ref() {
+            match classify_guarded(&self.policy, c) {
+                Decision::Allow => return Some(c),
+                Decision::Replace(replacement) => {
+                    self.replaced += 1;
+                    return Some(replacement);
+                }
+                Decision::Remove => self.removed += 1,
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.chars.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::with_policy;
+
+    #[test]
+    fn test_sanitize_chars() {
+        let mut chars = SanitizeChars::new("a🏴🏴b".chars());
+        assert_eq!(chars.next(), Some('a'));
+        assert_eq!(chars.next(), Some('b'));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.removed(), 2);
+        assert_eq!(chars.replaced(), 0);
+
+        // The active policy is copied when the adaptor is created
+        let chars = with_policy(Policy::new(&[0x61..=0x7A]), || {
+            SanitizeChars::new("aBc".chars())
+        });
+        assert_eq!(chars.collect::<String>(), "ac");
+
+        let upper = |c: char| match c {
+            'a'..='z' => Decision::Replace(c.to_ascii_uppercase()),
+            ' ' => Decision::Allow,
+            _ => Decision::Remove,
+        };
+        let mut chars = SanitizeChars::with_policy("hi there!".chars(), upper);
+        assert_eq!(chars.by_ref().collect::<String>(), "HI THERE");
+        assert_eq!((chars.removed(), chars.replaced()), (1, 7));
+    }
+}
@@ -0,0 +1,292 @@
+//! Chat messages, as sent to and received from OpenAI-style APIs.
+//!
+//! ```
+//! use langsan::{chat::{sanitize_messages, Message, Role}, Policy};
+//!
+//! let mut messages = vec![
+//!     Message::new(Role::System, "Be helpful."),
+//!     Message::new(Role::User, "<b>Hi</b>"),
+//! ];
+//! let input = Policy::default().forbid(['<', '>']);
+//! assert_eq!(sanitize_messages(&mut messages, &input, &Policy::default()), 1);
+//! ```
+use std::fmt;
+
+use crate::{cow::push_sanitized, report::all_blocks, CowStr, Policy, Report, SanitizedString};
+
+/// Who a [`Message`] is from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Role {
+    /// Instructions from the application.
+    System,
+    /// Instructions from the application, as newer APIs call them.
+    Developer,
+    /// Input from the user.
+    User,
+    /// Output from the model.
+    Assistant,
+    /// The result of a tool call.
+    Tool,
+}
+
+/// A chat message. Its content is sanitized with the active policy when it's
+/// created or deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message {
+    pub role: Role,
+    pub content: SanitizedString,
+    /// An optional name for the author, to tell apart participants with the
+    /// same role.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<SanitizedString>,
+}
+
+impl Message {
+    pub fn new(role: Role, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: CowStr::new(content.into()),
+            name: None,
+        }
+    }
+
+    /// Sanitize the content with `policy`, returning a [`Report`] if it was
+    /// modified.
+    fn sanitize_with(&mut self, policy: &Policy) -> Option<Report> {
+        let mut output = String::new();
+        let mut edits = Vec::new();
+        let report = push_sanitized(&self.content, policy, &mut output, &mut edits)?;
+        self.content = CowStr {
+            inner: output.into(),
+            edits,
+        };
+        Some(report)
+    }
+}
+
+/// Which [`Policy`] to sanitize each [`Role`]'s messages with, if any. By
+/// default, system and developer messages are left alone and the rest use the
+/// default policy.
+///
+/// ```
+/// use langsan::{chat::{Role, RolePolicies}, Policy};
+///
+/// let policies = RolePolicies::default()
+///     .policy(Role::User, Policy::default().forbid(['<', '>']))
+///     .policy(Role::Tool, Policy::new(&[0x20..=0x7E]));
+/// assert!(policies.get(Role::System).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RolePolicies {
+    system: Option<Policy>,
+    developer: Option<Policy>,
+    user: Option<Policy>,
+    assistant: Option<Policy>,
+    tool: Option<Policy>,
+}
+
+impl RolePolicies {
+    /// Sanitize `role`'s messages with `policy`.
+    pub fn policy(mut self, role: Role, policy: Policy) -> Self {
+        *self.get_mut(role) = Some(policy);
+        self
+    }
+
+    /// Leave `role`'s messages alone.
+    pub fn skip(mut self, role: Role) -> Self {
+        *self.get_mut(role) = None;
+        self
+    }
+
+    /// The policy for `role`'s messages, if they are sanitized.
+    pub fn get(&self, role: Role) -> Option<&Policy> {
+        match role {
+            Role::System => self.system.as_ref(),
+            Role::Developer => self.developer.as_ref(),
+            Role::User => self.user.as_ref(),
+            Role::Assistant => self.assistant.as_ref(),
+            Role::Tool => self.tool.as_ref(),
+        }
+    }
+
+    fn get_mut(&mut self, role: Role) -> &mut Option<Policy> {
+        match role {
+            Role::System => &mut self.system,
+            Role::Developer => &mut self.developer,
+            Role::User => &mut self.user,
+            Role::Assistant => &mut self.assistant,
+            Role::Tool => &mut self.tool,
+        }
+    }
+}
+
+impl Default for RolePolicies {
+    fn default() -> Self {
+        Self {
+            system: None,
+            developer: None,
+            user: Some(Policy::default()),
+            assistant: Some(Policy::default()),
+            tool: Some(Policy::default()),
+        }
+    }
+}
+
+/// What sanitizing a transcript changed, by message.
+#[derive(Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranscriptReport {
+    messages: Vec<(usize, Report)>,
+}
+
+impl TranscriptReport {
+    /// The index and [`Report`] of each modified message, in order.
+    pub fn messages(&self) -> impl Iterator<Item = (usize, &Report)> {
+        self.messages.iter().map(|(i, report)| (*i, report))
+    }
+
+    /// The number of messages modified.
+    pub fn modified(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// The number of bytes removed across all messages.
+    pub fn removed_bytes(&self) -> usize {
+        self.messages.iter().map(|(_, r)| r.removed_bytes()).sum()
+    }
+
+    /// The number of characters replaced across all messages.
+    pub fn replaced(&self) -> usize {
+        self.messages.iter().map(|(_, r)| r.replaced()).sum()
+    }
+
+    /// Returns true if no message was modified.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl fmt::Debug for TranscriptReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TranscriptReport")
+            .field("modified", &self.modified())
+            .field("removed_bytes", &self.removed_bytes())
+            .field("replaced", &self.replaced())
+            .field("blocks", &all_blocks(self.messages.iter().map(|(_, r)| r)))
+            .field("messages", &self.messages)
+            .finish()
+    }
+}
+
+/// Sanitize each message with its role's policy from `policies`, returning a
+/// report of what changed.
+pub fn sanitize_transcript(messages: &mut [Message], policies: &RolePolicies) -> TranscriptReport {
+    let mut report = TranscriptReport::default();
+    for (i, message) in messages.iter_mut().enumerate() {
+        if let Some(policy) = policies.get(message.role) {
+            if let Some(r) = message.sanitize_with(policy) {
+                report.messages.push((i, r));
+            }
+        }
+    }
+    report
+}
+
+/// Sanitize the content of user and tool messages with the `input` policy and
+/// assistant messages with the `output` policy. System and developer messages
+/// come from the application and are left alone. Returns the number of
+/// messages modified.
+pub fn sanitize_messages(messages: &mut [Message], input: &Policy, output: &Policy) -> usize {
+    let policies = RolePolicies::default()
+        .policy(Role::User, input.clone())
+        .policy(Role::Tool, input.clone())
+        .policy(Role::Assistant, output.clone());
+    sanitize_transcript(messages, &policies).modified()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_messages() {
+        let mut messages = vec![
+            Message::new(Role::System, "Reply in <b>HTML</b>"),
+            Message::new(Role::User, "Hi <script>"),
+            Message::new(Role::Assistant, "<b>Hello</b> {}"),
+            Message::new(Role::Tool, "{\"ok\":true}"),
+            Message::new(Role::User, "Hi🏴"),
+        ];
+        let input = Policy::default().forbid(['<', '>']);
+        let output = Policy::default().forbid(['{', '}']);
+        assert_eq!(sanitize_messages(&mut messages, &input, &output), 2);
+        assert_eq!(messages[0].content.as_ref(), "Reply in <b>HTML</b>");
+        assert_eq!(messages[3].content.as_ref(), "{\"ok\":true}");
+        #[cfg(not(feature = "verbose"))]
+        {
+            assert_eq!(messages[1].content.as_ref(), "Hi ");
+            assert_eq!(messages[2].content.as_ref(), "<b>Hello</b> ");
+            // Sanitized with the active policy when created
+            assert_eq!(messages[4].content.as_ref(), "Hi");
+        }
+        assert_eq!(sanitize_messages(&mut messages, &input, &output), 0);
+    }
+
+    #[test]
+    fn test_sanitize_transcript() {
+        let mut messages = vec![
+            Message::new(Role::System, "Use <tools>"),
+            Message::new(Role::User, "Hi <b>there</b>"),
+            Message::new(Role::Assistant, "Hello"),
+            Message::new(Role::Tool, "42 ok"),
+        ];
+        let policies = RolePolicies::default()
+            .policy(Role::User, Policy::default().forbid(['<', '>']))
+            .policy(Role::Tool, Policy::new(&[0x20..=0x3B]));
+        let report = sanitize_transcript(&mut messages, &policies);
+        assert_eq!(report.modified(), 2);
+        assert_eq!(
+            report.messages().map(|(i, _)| i).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        // From the first `<` to the last `>`, and the tool message's letters
+        assert_eq!(report.removed_bytes(), 12 + 2);
+        assert_eq!(report.replaced(), 0);
+        assert_eq!(messages[0].content.as_ref(), "Use <tools>");
+        // The verbose marker has letters the tool policy would remove again
+        #[cfg(not(feature = "verbose"))]
+        {
+            assert_eq!(messages[1].content.as_ref(), "Hi ");
+            assert!(sanitize_transcript(&mut messages, &policies).is_empty());
+        }
+
+        let policies = RolePolicies::default().skip(Role::User);
+        assert!(policies.get(Role::User).is_none());
+        assert_eq!(policies.get(Role::Tool), Some(&Policy::default()));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let json = r#"[{"role":"system","content":"Be brief."},{"role":"user","content":"Hi🏴","name":"alice"}]"#;
+        let messages: Vec<Message> = serde_json::from_str(json).unwrap();
+        assert_eq!(messages[0].role, Role::System);
+        assert_eq!(messages[1].role, Role::User);
+        assert_eq!(messages[1].name.as_deref(), Some("alice"));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(
+            serde_json::to_string(&messages).unwrap(),
+            json.replace("🏴", "")
+        );
+        assert!(serde_json::from_str::<Message>(r#"{"role":"robot","content":""}"#).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+/// Terminal highlighting of what sanitization removes.
+use crate::Report;
+
+/// Starts red, inverse text.
+const REMOVED: &str = "\x1b[7;31m";
+/// Ends highlighted text.
+const RESET: &str = "\x1b[0m";
+
+impl Report {
+    /// `original` with the span this report removes highlighted in red and
+    /// inverse with ANSI escapes, so suspicious input can be eyeballed in a
+    /// terminal. Invisible characters in the span, such as tag characters and
+    /// bidi controls, are shown as `<U+XXXX>`.
+    ///
+    /// ```
+    /// use langsan::sanitize_outcome;
+    ///
+    /// let input = "hi\u{E0041}!";
+    /// let report = sanitize_outcome(input).report().cloned().unwrap();
+    /// assert_eq!(report.highlight(input), "hi\x1b[7;31m<U+E0041>\x1b[0m!");
+    /// ```
+    pub fn highlight(&self, original: &str) -> String {
+        let Some(removed) = self.removed() else {
+            return original.to_string();
+        };
+        let mut out = String::with_capacity(original.len() + 16);
+        out.push_str(&original[..removed.start]);
+        out.push_str(REMOVED);
+        for c in original[removed.clone()].chars() {
+            if is_invisible(c) {
+                out.push_str(&format!("<U+{:04X}>", c as u32));
+            } else {
+                out.push(c);
+            }
+        }
+        out.push_str(RESET);
+        out.push_str(&original[removed.end..]);
+        out
+    }
+}
+
+/// Returns true if `c` doesn't render visibly on its own.
+fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c as u32,
+            0x00AD
+                | 0x200B..=0x200F
+                | 0x202A..=0x202E
+                | 0x2060..=0x2064
+                | 0x2066..=0x2069
+                | 0xFE00..=0xFE0F
+                | 0xFEFF
+                | 0xE0000..=0xE007F
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sanitize_outcome_with, Decision, Policy};
+
+    #[test]
+    fn test_highlight() {
+        let policy = Policy::new(&[0x20..=0x7E]);
+        let highlight = |s| match sanitize_outcome_with(s, &policy).report() {
+            Some(report) => report.highlight(s),
+            None => s.to_string(),
+        };
+        assert_eq!(highlight("clean"), "clean");
+        assert_eq!(highlight("a🏴b\u{202E}c"), "a\x1b[7;31m🏴b<U+202E>\x1b[0mc");
+        // Replacements alone aren't highlighted
+        let tabs = |c: char| match c {
+            '\t' => Decision::Replace(' '),
+            _ => Decision::Allow,
+        };
+        let outcome = sanitize_outcome_with("a\tb", &tabs);
+        assert_eq!(outcome.report().unwrap().highlight("a\tb"), "a\tb");
+    }
+}
@@ -0,0 +1,336 @@
+/// Parallel sanitization of datasets.
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    num::NonZeroUsize,
+    panic,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{policy::with_current, sanitize_outcome_with, CharPolicy, Outcome, Policy, Stats};
+
+/// The number of lines each thread sanitizes at a time in
+/// [`Corpus::sanitize_lines`].
+const BATCH: usize = 1024;
+
+/// Sanitizes datasets across threads, keeping records in order, for preparing
+/// training data. Each record is sanitized by itself, so a removed span never
+/// extends past the end of one. What was changed is returned as [`Stats`].
+///
+/// Records are sanitized as text, so JSON escapes such as `\u202e` are left
+/// alone. Parse records and use `sanitize_json`, with the `serde` feature, to
+/// handle those.
+///
+/// ```
+/// use langsan::{Corpus, Policy};
+///
+/// let corpus = Corpus::new().policy(Policy::new(&[0x20..=0x7E])).threads(2);
+/// let mut output = Vec::new();
+/// let stats = corpus
+///     .sanitize_lines("{\"text\": \"hi\"}\n{\"text\": \"🏴\"}\n".as_bytes(), &mut output)
+///     .unwrap();
+/// assert_eq!((stats.inputs(), stats.modified()), (2, 1));
+/// # #[cfg(not(feature = "verbose"))]
+/// assert_eq!(output, b"{\"text\": \"hi\"}\n{\"text\": \"\"}\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    policy: Policy,
+    threads: NonZeroUsize,
+}
+
+impl Default for Corpus {
+    /// A copy of the active policy and a thread per available CPU.
+    fn default() -> Self {
+        Self {
+            policy: with_current(Policy::clone),
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+impl Corpus {
+    /// Sanitize with a copy of the active policy and a thread per available
+    /// CPU.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sanitize with `policy`.
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Use at most `threads` threads. Zero is treated as one.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = NonZeroUsize::new(threads).unwrap_or(NonZeroUsize::MIN);
+        self
+    }
+
+    /// Sanitize `records` in place, splitting them between threads.
+    pub fn sanitize_records(&self, records: &mut [String]) -> Stats {
+        let chunk = records.len().div_ceil(self.threads.get()).max(1);
+        thread::scope(|scope| {
+            let workers: Vec<_> = records
+                .chunks_mut(chunk)
+                .map(|records| scope.spawn(|| sanitize_records(records, &self.policy)))
+                .collect();
+            let mut stats = Stats::new();
+            for worker in workers {
+                match worker.join() {
+                    Ok(worker) => stats.merge(&worker),
+                    Err(e) => panic::resume_unwind(e),
+                }
+            }
+            stats
+        })
+    }
+
+    /// Sanitize each line of `input`, such as a JSONL dataset, onto `output`
+    /// in order. Lines are read in batches, so memory use is bounded however
+    /// long the input is. Line endings are kept and invalid UTF-8 is replaced
+    /// with U+FFFD first.
+    pub fn sanitize_lines(
+        &self,
+        mut input: impl BufRead,
+        mut output: impl Write,
+    ) -> io::Result<Stats> {
+        let batch = self.threads.get() * BATCH;
+        let mut stats = Stats::new();
+        let mut records = Vec::with_capacity(batch);
+        let mut endings: Vec<&[u8]> = Vec::with_capacity(batch);
+        let mut buf = Vec::new();
+        loop {
+            records.clear();
+            endings.clear();
+            while records.len() < batch {
+                buf.clear();
+                if input.read_until(b'\n', &mut buf)? == 0 {
+                    break;
+                }
+                let (line, ending) = split_ending(&buf);
+                records.push(String::from_utf8_lossy(line).into_owned());
+                endings.push(match ending {
+                    b"\r\n" => b"\r\n",
+                    b"\n" => b"\n",
+                    _ => b"",
+                });
+            }
+            if records.is_empty() {
+                break;
+            }
+            stats.merge(&self.sanitize_records(&mut records));
+            for (record, ending) in records.iter().zip(&endings) {
+                output.write_all(record.as_bytes())?;
+                output.write_all(ending)?;
+            }
+        }
+        output.flush()?;
+        Ok(stats)
+    }
+
+    /// Sanitize every file under `dir`, line by line, writing each to the same
+    /// relative path under `out_dir`, which may be `dir`. Files are split
+    /// between threads. Symbolic links are skipped.
+    ///
+    /// Each output is written to a temporary file and renamed over the
+    /// original, so an interruption can't leave it truncated. The first error
+    /// stops the remaining files from being sanitized.
+    pub fn sanitize_dir(
+        &self,
+        dir: impl AsRef<Path>,
+        out_dir: impl AsRef<Path>,
+    ) -> io::Result<Stats> {
+        let (dir, out_dir) = (dir.as_ref(), out_dir.as_ref());
+        let mut files = Vec::new();
+        find_files(dir, &mut files)?;
+        files.sort();
+
+        let next = AtomicUsize::new(0);
+        let worker = || -> io::Result<Stats> {
+            let mut stats = Stats::new();
+            while let Some(path) = files.get(next.fetch_add(1, Ordering::Relaxed)) {
+                let out_path = out_dir.join(path.strip_prefix(dir).unwrap_or(path));
+                let file_stats = sanitize_file(path, &out_path, &self.policy).map_err(|e| {
+                    // Stop the other workers.
+                    next.store(files.len(), Ordering::Relaxed);
+                    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+                })?;
+                stats.merge(&file_stats);
+            }
+            Ok(stats)
+        };
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.get().min(files.len()))
+                .map(|_| scope.spawn(worker))
+                .collect();
+            let mut stats = Stats::new();
+            for worker in workers {
+                match worker.join() {
+                    Ok(worker) => stats.merge(&worker?),
+                    Err(e) => panic::resume_unwind(e),
+                }
+            }
+            Ok(stats)
+        })
+    }
+}
+
+/// Sanitize each of `records` in place with `policy`.
+fn sanitize_records(records: &mut [String], policy: &Policy) -> Stats {
+    let mut stats = Stats::new();
+    for record in records {
+        match sanitize_outcome_with(record, policy) {
+            Outcome::Modified { output, report } => {
+                stats.record(Some(&report));
+                *record = output;
+            }
+            Outcome::Clean(_) => stats.record(None),
+        }
+    }
+    stats
+}
+
+/// Add the paths of the files under `dir` to `files`.
+fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            find_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Sanitize the file at `path` line by line onto `out_path`, through a
+/// temporary file.
+fn sanitize_file(path: &Path, out_path: &Path, policy: &Policy) -> io::Result<Stats> {
+    let input = fs::read(path)?;
+    let mut output = Vec::with_capacity(input.len());
+    let stats = sanitize_bytes_lines(&input, policy, &mut output)?;
+    if let Some(parent) = out_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(out_path, |file| file.write_all(&output))?;
+    Ok(stats)
+}
+
+/// Split the line ending off a `line`.
+fn split_ending(line: &[u8]) -> (&[u8], &[u8]) {
+    let content = line.strip_suffix(b"\n").unwrap_or(line);
+    let content = content.strip_suffix(b"\r").unwrap_or(content);
+    line.split_at(content.len())
+}
+
+/// Sanitize `input` line by line onto `output`, replacing invalid UTF-8 with
+/// U+FFFD first.
+pub(crate) fn sanitize_bytes_lines<P>(
+    input: &[u8],
+    policy: &P,
+    output: &mut impl Write,
+) -> io::Result<Stats>
+where
+    P: CharPolicy + ?Sized,
+{
+    let mut stats = Stats::new();
+    for line in input.split_inclusive(|b| *b == b'\n') {
+        let (content, ending) = split_ending(line);
+        let content = String::from_utf8_lossy(content);
+        output.write_all(stats.sanitize_with(&content, policy).as_bytes())?;
+        output.write_all(ending)?;
+    }
+    Ok(stats)
+}
+
+/// Write `path` with `write` through a temporary file next to it, renamed over
+/// it once it's complete.
+pub(crate) fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut fs::File) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".langsan.tmp");
+    let result = fs::File::create(&tmp).and_then(|mut file| {
+        write(&mut file)?;
+        file.sync_all()
+    });
+    match result.and_then(|_| fs::rename(&tmp, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            fs::remove_file(&tmp).ok();
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_lines() {
+        let policy = Policy::new(vec![0x20..=0x7E, 0x0A..=0x0A]).verbose(false);
+        let mut input = Vec::new();
+        for i in 0..5000 {
+            input.extend_from_slice(format!("{i}\u{E0041}\r\n").as_bytes());
+        }
+        input.extend_from_slice(b"bad\xFF");
+        let mut output = Vec::new();
+        let corpus = Corpus::new().policy(policy.clone()).threads(3);
+        let stats = corpus
+            .sanitize_lines(input.as_slice(), &mut output)
+            .unwrap();
+        assert_eq!((stats.inputs(), stats.modified()), (5001, 5001));
+        assert_eq!(stats.removed_by_block()["Tags"], 5000);
+
+        let mut expected = String::new();
+        for i in 0..5000 {
+            expected.push_str(&format!("{i}\r\n"));
+        }
+        expected.push_str("bad");
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+        // Fewer records than threads
+        let mut records = vec!["a🏴".to_string()];
+        let stats = Corpus::new()
+            .policy(policy)
+            .threads(0)
+            .sanitize_records(&mut records);
+        assert_eq!((records[0].as_str(), stats.modified()), ("a", 1));
+        assert_eq!(Corpus::new().sanitize_records(&mut []), Stats::new());
+    }
+
+    #[test]
+    fn test_sanitize_dir() {
+        let dir = std::env::temp_dir().join(format!("langsan-corpus-{}", std::process::id()));
+        let out_dir = dir.join("out");
+        fs::create_dir_all(dir.join("in/nested")).unwrap();
+        fs::write(dir.join("in/a.txt"), "a🏴\nb\n").unwrap();
+        fs::write(dir.join("in/nested/b.jsonl"), "{}\u{E0041}").unwrap();
+        fs::write(dir.join("in/c.txt"), "clean").unwrap();
+
+        let policy = Policy::new(vec![0x20..=0x7E, 0x0A..=0x0A]).verbose(false);
+        let corpus = Corpus::new().policy(policy).threads(2);
+        let stats = corpus.sanitize_dir(dir.join("in"), &out_dir).unwrap();
+        assert_eq!((stats.inputs(), stats.modified()), (4, 2));
+        assert_eq!(fs::read_to_string(out_dir.join("a.txt")).unwrap(), "a\nb\n");
+        assert_eq!(
+            fs::read_to_string(out_dir.join("nested/b.jsonl")).unwrap(),
+            "{}"
+        );
+        assert_eq!(fs::read_to_string(out_dir.join("c.txt")).unwrap(), "clean");
+
+        // In place
+        corpus.sanitize_dir(dir.join("in"), dir.join("in")).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("in/a.txt")).unwrap(), "a\nb\n");
+
+        assert!(corpus.sanitize_dir(dir.join("missing"), &out_dir).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,16 +1,153 @@
-use std::{borrow::Cow, ops::Deref};
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Bound, Deref, Range, RangeBounds},
+    str::FromStr,
+};
 
-use crate::san::sanitize;
+use crate::{
+    emoji,
+    policy::{with_current, CharPolicy, Marker},
+    report::all_blocks,
+    san::{check, render, sanitize_strict, scan},
+    Error, Report,
+};
 
 /// A wrapper around `Cow<str>` that [`sanitize`]s the string when it is
 /// created. The string is only copied if it's necessary.
 ///
 /// This is guaranteed to be a valid UTF-8 string with only the characters that
 /// are enabled by feature.
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// If the policy doesn't mark removed text, where it was is remembered, so the
+/// alternate format, `{:#}`, can show markers for logs while the model sees the
+/// quiet version.
+///
+/// ```
+/// # use langsan::CowStr;
+/// let s = CowStr::from("Hi🏴!");
+/// # #[cfg(not(feature = "verbose"))]
+/// # {
+/// assert_eq!(format!("{s}"), "Hi!");
+/// assert_eq!(format!("{s:#}"), "Hi[4 BYTES SANITIZED]!");
+/// # }
+/// ```
+///
+/// `Debug` shows what sanitization changed, as well as the string.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
 pub struct CowStr<'a> {
     pub(crate) inner: Cow<'a, str>,
+    /// The changes sanitization made, in order.
+    pub(crate) edits: Vec<Edit>,
+}
+
+/// A change sanitization made to a [`CowStr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Edit {
+    /// Where removed text was in the sanitized string, if it isn't marked.
+    hidden_at: Option<usize>,
+    report: Report,
+}
+
+/// Sanitize `s` with `policy` onto `out`, recording the change in `edits`.
+/// Returns a [`Report`] if `s` was modified, otherwise nothing is pushed.
+pub(crate) fn push_sanitized<P>(
+    s: &str,
+    policy: &P,
+    out: &mut String,
+    edits: &mut Vec<Edit>,
+) -> Option<Report>
+where
+    P: CharPolicy + ?Sized,
+{
+    let report = scan(s, policy)?;
+    out.reserve(s.len());
+    let at = render(s, policy, &report, out);
+    edits.push(Edit {
+        hidden_at: at.filter(|_| *policy.marker() == Marker::None),
+        report: report.clone(),
+    });
+    Some(report)
+}
+
+/// Check that `s` is still clean under the active policy, that is, that
+/// sanitizing it again wouldn't change it, for tests asserting that nothing
+/// got past [`CowStr`]. Returns an [`Error::Disallowed`] for the first
+/// offending character.
+///
+/// A `CowStr` sanitized under a more permissive policy than the active one can
+/// fail, as can one with markers or replacements the policy wouldn't allow
+/// itself.
+///
+/// ```
+/// # use langsan::{verify, CowStr};
+/// let s = CowStr::from("Hi\u{E0041}!");
+/// assert_eq!(verify(&s), Ok(()));
+/// ```
+pub fn verify(s: &CowStr) -> Result<(), Error> {
+    with_current(|policy| verify_with(s, policy))
+}
+
+/// [`verify`] with an explicit `policy` rather than the active one.
+pub fn verify_with<P>(s: &CowStr, policy: &P) -> Result<(), Error>
+where
+    P: CharPolicy + ?Sized,
+{
+    check(s, policy)
+}
+
+/// `range` as a `Range` into a string of `len` bytes, or `None` if it
+/// overflows.
+fn bounds(range: impl RangeBounds<usize>, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    Some(start..end)
+}
+
+/// An owned [`CowStr`].
+pub type SanitizedString = CowStr<'static>;
+
+/// Always a plain string, so this works with formats that aren't
+/// self-describing as well as JSON.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CowStr<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.inner)
+    }
+}
+
+/// A plain string, so APIs using `CowStr` can still generate schemas.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CowStr<'_> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -19,8 +156,201 @@ impl<'de, 'a> serde::Deserialize<'de> for CowStr<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        let cow: Cow<'a, str> = Cow::deserialize(deserializer)?;
-        Ok(cow.into())
+        deserializer.deserialize_string(OwnedVisitor)
+    }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_string(InPlaceVisitor(place))
+    }
+}
+
+/// Deserializes into an existing [`CowStr`], reusing its buffer if owned.
+#[cfg(feature = "serde")]
+struct InPlaceVisitor<'p, 'a>(&'p mut CowStr<'a>);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for InPlaceVisitor<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let buf = match &mut self.0.inner {
+            Cow::Owned(buf) => buf,
+            inner => {
+                *inner = Cow::Owned(String::with_capacity(v.len()));
+                inner.to_mut()
+            }
+        };
+        buf.clear();
+        let edits = &mut self.0.edits;
+        edits.clear();
+        with_current(|policy| {
+            if push_sanitized(v, policy, buf, edits).is_none() {
+                buf.push_str(v);
+            }
+        });
+        Ok(())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        *self.0 = v.into();
+        Ok(())
+    }
+}
+
+/// Deserializes an owned [`CowStr`], reusing the buffer of owned input.
+#[cfg(feature = "serde")]
+struct OwnedVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for OwnedVisitor {
+    type Value = CowStr<'static>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(CowStr::from(v).into_static())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.into())
+    }
+}
+
+/// Deserializes a [`CowStr`] borrowing from the input when possible.
+#[cfg(feature = "serde")]
+struct BorrowedVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for BorrowedVisitor {
+    type Value = CowStr<'de>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.into())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        OwnedVisitor.visit_str(v)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.into())
+    }
+}
+
+/// Deserializes an owned [`CowStr`], failing if it would be sanitized.
+#[cfg(feature = "serde")]
+struct StrictVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for StrictVisitor {
+    type Value = CowStr<'static>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a string that does not require sanitization")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        sanitize_strict(v).map_err(E::custom)?;
+        Ok(CowStr::clean(v.to_owned().into()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        sanitize_strict(&v).map_err(E::custom)?;
+        Ok(CowStr::clean(v.into()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> CowStr<'a> {
+    /// Deserialize a `CowStr`, failing with a descriptive error rather than
+    /// modifying the string if it would be sanitized. For APIs which must
+    /// reject bad input, use it with `deserialize_with`:
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Request<'a> {
+    ///     #[serde(deserialize_with = "CowStr::deserialize_strict")]
+    ///     prompt: CowStr<'a>,
+    /// }
+    ///
+    /// let err = serde_json::from_str::<Request>(r#"{"prompt":"Hi \uD83C\uDFF4"}"#).unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "disallowed character U+1F3F4 at byte 3 at line 1 column 27"
+    /// );
+    /// ```
+    pub fn deserialize_strict<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_string(StrictVisitor)
+    }
+
+    /// Deserialize a `CowStr` borrowing from the input if it's clean and the
+    /// format allows it (e.g. JSON strings without escapes), so nothing is
+    /// copied. The [`Deserialize`] impl always copies so it can produce any
+    /// lifetime, including `'static`. To borrow in a derived struct, use:
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// #[derive(serde::Deserialize)]
+    /// struct Message<'a> {
+    ///     #[serde(borrow, deserialize_with = "CowStr::deserialize_borrowed")]
+    ///     content: CowStr<'a>,
+    /// }
+    ///
+    /// let json = r#"{"content":"Hello, world!"}"#;
+    /// let message: Message = serde_json::from_str(json).unwrap();
+    /// assert!(message.content.is_borrowed());
+    /// ```
+    ///
+    /// [`Deserialize`]: serde::Deserialize
+    pub fn deserialize_borrowed<'de: 'a, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BorrowedVisitor)
     }
 }
 
@@ -30,11 +360,36 @@ impl<'a> CowStr<'a> {
         inner.into()
     }
 
+    /// An empty, owned `CowStr` with room for at least `capacity` bytes, for
+    /// accumulating with [`push_str`] without reallocating.
+    ///
+    /// ```
+    /// # use langsan::SanitizedString;
+    /// let mut response = SanitizedString::with_capacity(64);
+    /// response.push_str("Hello, ");
+    /// response.push_str("world!");
+    /// assert!(response.capacity() >= 64);
+    /// ```
+    ///
+    /// [`push_str`]: CowStr::push_str
+    pub fn with_capacity(capacity: usize) -> Self {
+        CowStr::clean(Cow::Owned(String::with_capacity(capacity)))
+    }
+
+    /// Strict version of [`CowStr::new`] returning an [`Error`] rather than
+    /// modifying the string if it would be sanitized.
+    pub fn try_new(s: impl Into<Cow<'a, str>>) -> Result<Self, Error> {
+        let inner: Cow<'a, str> = s.into();
+        sanitize_strict(&inner)?;
+        Ok(CowStr::clean(inner))
+    }
+
     /// Converts the `CowStr` into a `CowStr` with a `'static` lifetime. This
     /// will copy the string if it's not already owned.
     pub fn into_static(self) -> CowStr<'static> {
         CowStr {
-            inner: self.into_owned().into(),
+            inner: self.inner.into_owned().into(),
+            edits: self.edits,
         }
     }
 
@@ -46,14 +401,163 @@ impl<'a> CowStr<'a> {
     /// sanitized before being appended. This will take ownership of the string
     /// if it's not already owned.
     pub fn push_str(&mut self, s: &str) {
-        if let Some(sanitized) = sanitize(s) {
-            if !sanitized.is_empty() {
-                self.inner.to_mut().push_str(&sanitized);
+        if s.is_empty() {
+            return;
+        }
+        // Sanitize straight onto the end, only copying `s` as is if it's clean.
+        let inner = self.inner.to_mut();
+        let edits = &mut self.edits;
+        with_current(|policy| {
+            if push_sanitized(s, policy, inner, edits).is_none() {
+                inner.push_str(s);
             }
-        } else {
-            if !s.is_empty() {
-                self.inner.to_mut().push_str(s);
+        });
+    }
+
+    /// The number of bytes the string can hold without reallocating, or 0 if
+    /// it's borrowed.
+    pub fn capacity(&self) -> usize {
+        match &self.inner {
+            Cow::Owned(s) => s.capacity(),
+            Cow::Borrowed(_) => 0,
+        }
+    }
+
+    /// Reserve room for at least `additional` more bytes, as for
+    /// [`String::reserve`]. This will take ownership of the string if it's not
+    /// already owned.
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.to_mut().reserve(additional);
+    }
+
+    /// Shrink the capacity to the length of the string, if it's owned.
+    pub fn shrink_to_fit(&mut self) {
+        if let Cow::Owned(s) = &mut self.inner {
+            s.shrink_to_fit();
+        }
+    }
+
+    /// Transform the string with `f`, such as a template or a change of case,
+    /// and sanitize the result, so arbitrary processing can't produce a
+    /// `CowStr` with disallowed characters.
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// let name = CowStr::from("world");
+    /// let greeting = name.map(|s| format!("Hello, {s}!\u{1F3F4}"));
+    /// # #[cfg(not(feature = "verbose"))]
+    /// assert_eq!(greeting.as_ref(), "Hello, world!");
+    /// ```
+    pub fn map(&self, f: impl FnOnce(&str) -> String) -> SanitizedString {
+        f(&self.inner).into()
+    }
+
+    /// A borrowed view of the bytes in `range`, like `&s[range]`, that is
+    /// still a `CowStr`, so substrings of sanitized text keep the guarantee.
+    /// Removed text that wasn't marked within the view is still shown by
+    /// `{:#}`.
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// let s = CowStr::from("Hello, world!");
+    /// assert_eq!(s.slice(7..12), CowStr::from("world"));
+    /// assert!(s.slice(..5).is_borrowed());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If [`get_slice`] would return `None`.
+    ///
+    /// [`get_slice`]: CowStr::get_slice
+    #[track_caller]
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> CowStr<'_> {
+        let (start, end) = (range.start_bound().cloned(), range.end_bound().cloned());
+        match self.get_slice((start, end)) {
+            Some(slice) => slice,
+            None => panic!(
+                "byte range {:?} is out of bounds or not on a boundary of {:?}",
+                (start, end),
+                self.inner
+            ),
+        }
+    }
+
+    /// [`slice`], or `None` if either end of `range` is out of bounds or not
+    /// on a char boundary. With the `emoji-full` feature, it's also `None` if
+    /// the view would cut an emoji joiner off from its sequence, since a lone
+    /// joiner would have been removed.
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// let s = CowStr::from("Hello");
+    /// assert_eq!(s.get_slice(1..3).as_deref(), Some("el"));
+    /// assert_eq!(s.get_slice(2..9), None);
+    /// ```
+    ///
+    /// [`slice`]: CowStr::slice
+    pub fn get_slice(&self, range: impl RangeBounds<usize>) -> Option<CowStr<'_>> {
+        let Range { start, end } = bounds(range, self.inner.len())?;
+        let inner = self.inner.get(start..end)?;
+        if cfg!(feature = "emoji-full") && emoji::cuts_sequence(inner) {
+            return None;
+        }
+        Some(self.view(inner))
+    }
+
+    /// The lines of the string, as for [`str::lines`], each a borrowed
+    /// `CowStr`, so sanitized transcripts can be processed line by line
+    /// without sanitizing them again.
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// let transcript = CowStr::from("user: hi\nassistant: hello\n");
+    /// let lines: Vec<CowStr> = transcript.lines().collect();
+    /// assert_eq!(lines, [CowStr::from("user: hi"), CowStr::from("assistant: hello")]);
+    /// assert!(lines.iter().all(CowStr::is_borrowed));
+    /// ```
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = CowStr<'_>> + '_ {
+        self.inner.lines().map(|line| self.view(line))
+    }
+
+    /// The pieces of the string separated by `sep`, as for [`str::split`],
+    /// each a borrowed `CowStr`. With the `emoji-full` feature, emoji joiners
+    /// cut off from their sequence at either end of a piece are left out.
+    ///
+    /// ```
+    /// # use langsan::CowStr;
+    /// let csv = CowStr::from("a,b,,c");
+    /// let fields: Vec<CowStr> = csv.split(",").collect();
+    /// assert_eq!(fields, ["a", "b", "", "c"].map(CowStr::from));
+    /// ```
+    pub fn split<'s>(&'s self, sep: &'s str) -> impl Iterator<Item = CowStr<'s>> + 's {
+        self.inner.split(sep).map(|piece| {
+            if cfg!(feature = "emoji-full") {
+                self.view(emoji::trim_cut(piece))
+            } else {
+                self.view(piece)
             }
+        })
+    }
+
+    /// `part`, which must be a substring of this string, as a borrowed
+    /// `CowStr` with the hidden removals strictly within it.
+    fn view<'s>(&'s self, part: &'s str) -> CowStr<'s> {
+        let start = part.as_ptr() as usize - self.inner.as_ptr() as usize;
+        let end = start + part.len();
+        let edits = self
+            .edits
+            .iter()
+            .filter_map(|edit| match edit.hidden_at {
+                Some(at) if start < at && at < end => Some(Edit {
+                    hidden_at: Some(at - start),
+                    report: edit.report.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+        CowStr {
+            inner: Cow::Borrowed(part),
+            edits,
         }
     }
 
@@ -72,22 +576,42 @@ impl<'a> CowStr<'a> {
     pub fn into_inner(self) -> Cow<'a, str> {
         self.inner
     }
+
+    /// Wrap `inner`, which must already be sanitized.
+    pub(crate) const fn clean(inner: Cow<'a, str>) -> Self {
+        CowStr {
+            inner,
+            edits: Vec::new(),
+        }
+    }
 }
 
-impl<'a> Into<Cow<'a, str>> for CowStr<'a> {
-    fn into(self) -> Cow<'a, str> {
-        self.into_inner()
+impl CowStr<'static> {
+    /// A `CowStr` of a literal checked by [`cowstr!`], without scanning it.
+    ///
+    /// [`cowstr!`]: crate::cowstr
+    #[doc(hidden)]
+    pub const fn __from_clean_literal(s: &'static str) -> Self {
+        CowStr::clean(Cow::Borrowed(s))
+    }
+}
+
+impl<'a> From<CowStr<'a>> for Cow<'a, str> {
+    fn from(s: CowStr<'a>) -> Self {
+        s.into_inner()
     }
 }
 
 impl<'a> From<Cow<'a, str>> for CowStr<'a> {
     fn from(cow: Cow<'a, str>) -> Self {
-        if let Some(sanitized) = sanitize(cow.as_ref()) {
-            CowStr {
+        let mut sanitized = String::new();
+        let mut edits = Vec::new();
+        match with_current(|policy| push_sanitized(&cow, policy, &mut sanitized, &mut edits)) {
+            Some(_) => CowStr {
                 inner: sanitized.into(),
-            }
-        } else {
-            CowStr { inner: cow }
+                edits,
+            },
+            None => CowStr::clean(cow),
         }
     }
 }
@@ -119,15 +643,76 @@ impl<'a> Deref for CowStr<'a> {
     }
 }
 
-impl std::fmt::Display for CowStr<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner.fmt(f)
+/// With the alternate flag, `{:#}`, removed text the policy didn't mark is
+/// marked as with [`Marker::Bytes`].
+impl fmt::Display for CowStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() || self.edits.iter().all(|edit| edit.hidden_at.is_none()) {
+            return self.inner.fmt(f);
+        }
+        let mut marked = String::with_capacity(self.inner.len() + 24 * self.edits.len());
+        let mut last = 0;
+        for edit in &self.edits {
+            if let Some(at) = edit.hidden_at {
+                marked.push_str(&self.inner[last..at]);
+                Marker::Bytes.push(edit.report.removed_bytes(), &mut marked);
+                last = at;
+            }
+        }
+        marked.push_str(&self.inner[last..]);
+        marked.fmt(f)
+    }
+}
+
+impl fmt::Debug for CowStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reports = self.edits.iter().map(|edit| &edit.report);
+        f.debug_struct("CowStr")
+            .field("value", &self.inner)
+            .field("modified", &!self.edits.is_empty())
+            .field(
+                "removed_bytes",
+                &reports.clone().map(Report::removed_bytes).sum::<usize>(),
+            )
+            .field(
+                "replaced",
+                &reports.clone().map(Report::replaced).sum::<usize>(),
+            )
+            .field("blocks", &all_blocks(reports))
+            .finish()
+    }
+}
+
+/// Only the strings are compared, not what sanitization removed from them.
+impl PartialEq for CowStr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for CowStr<'_> {}
+
+impl Hash for CowStr<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+/// Sanitizes `s`. Use [`CowStr::try_new`] to reject it instead.
+impl FromStr for CowStr<'static> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CowStr::from(s).into_static())
     }
 }
 
 #[cfg(test)]
+// The tests count bytes explicitly
+#[allow(clippy::needless_as_bytes)]
 mod tests {
     use super::*;
+    use crate::{with_policy, Decision, Policy};
 
     #[test]
     #[cfg(not(feature = "emoticons-emoji"))]
@@ -155,6 +740,8 @@ mod tests {
         let s: CowStr<'static> = s.into_static();
         let s: String = s.into_owned();
         let s = CowStr::from(s);
+        #[cfg(feature = "verbose")]
+        assert_eq!(s.deref(), "Hello, [4 BYTES SANITIZED]world!");
         #[cfg(not(feature = "verbose"))]
         assert_eq!(s.deref(), "Hello, world!");
         #[cfg(not(feature = "verbose"))]
@@ -171,6 +758,105 @@ mod tests {
         assert_eq!(s.as_ref(), "Hello, [12 BYTES SANITIZED]world!");
     }
 
+    #[test]
+    fn test_slice() {
+        let policy = Policy::new(&[0x20..=0x7E]).verbose(false);
+        with_policy(policy, || {
+            let mut s = CowStr::from("Hi🏴,");
+            s.push_str(" world🏴!");
+            assert_eq!(s.as_ref(), "Hi, world!");
+            let world = s.slice(4..);
+            assert!(world.is_borrowed());
+            assert_eq!(world.as_ref(), "world!");
+            // Only the hidden removals within the view are kept
+            assert_eq!(format!("{world:#}"), "world[4 BYTES SANITIZED]!");
+            assert_eq!(format!("{:#}", s.slice(1..=2)), "i[4 BYTES SANITIZED],");
+            assert_eq!(format!("{:#}", s.slice(..2)), "Hi");
+            assert_eq!(s.slice(..).as_ref(), s.as_ref());
+            assert_eq!(s.slice(3..3).as_ref(), "");
+
+            assert_eq!(s.get_slice(4..11), None);
+            assert_eq!(s.get_slice(..=usize::MAX), None);
+            assert_eq!(
+                s.get_slice((Bound::Excluded(usize::MAX), Bound::Unbounded)),
+                None
+            );
+        });
+
+        with_policy(Policy::new(&[0x20..=0x7E, 0xA0..=0xFF]), || {
+            let s = CowStr::from("naïve");
+            assert_eq!(s.get_slice(..3), None);
+            assert!(std::panic::catch_unwind(|| s.slice(3..)).is_err());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "emoji-full")]
+    fn test_slice_emoji() {
+        let s = CowStr::from("\u{1F469}\u{200D}\u{1F4BB} 1\u{FE0F}\u{20E3}");
+        assert_eq!(s.chars().count(), 7);
+        assert_eq!(s.get_slice(..4), Some(CowStr::from("\u{1F469}")));
+        assert_eq!(s.get_slice(..7), None);
+        assert_eq!(s.get_slice(4..), None);
+        assert_eq!(s.get_slice(13..), None);
+        assert_eq!(s.get_slice(12..).as_deref(), Some("1\u{FE0F}\u{20E3}"));
+    }
+
+    #[test]
+    fn test_lines() {
+        let policy = Policy::new(&[0x0A..=0x0A, 0x20..=0x7E]).verbose(false);
+        with_policy(policy, || {
+            let mut s = CowStr::from("user: hi🏴!\n");
+            s.push_str("assistant: hello\n\nbye🏴\u{200B}");
+            let lines: Vec<String> = s.lines().map(|line| format!("{line:#}")).collect();
+            assert_eq!(
+                lines,
+                [
+                    "user: hi[4 BYTES SANITIZED]!",
+                    "assistant: hello",
+                    "",
+                    "bye"
+                ]
+            );
+            assert!(s.lines().all(|line| line.is_borrowed()));
+            assert_eq!(s.lines().next_back().unwrap().as_ref(), "bye");
+
+            let words: Vec<String> = s.split(" ").map(|word| format!("{word:#}")).collect();
+            assert_eq!(
+                words,
+                [
+                    "user:",
+                    "hi[4 BYTES SANITIZED]!\nassistant:",
+                    "hello\n\nbye"
+                ]
+            );
+            assert_eq!(CowStr::from("").split(",").count(), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "emoji-full")]
+    fn test_split_emoji() {
+        let s = CowStr::from("\u{1F469}\u{200D}\u{1F4BB}\u{200D}\u{1F469}!");
+        let pieces: Vec<CowStr> = s.split("\u{1F4BB}").collect();
+        assert_eq!(pieces, ["\u{1F469}", "\u{1F469}!"].map(CowStr::from));
+        assert!(pieces.iter().all(|piece| verify(piece).is_ok()));
+    }
+
+    #[test]
+    fn test_try_new() {
+        let s = CowStr::try_new("Hello, world!").unwrap();
+        assert!(s.is_borrowed());
+        assert_eq!(
+            CowStr::try_new("Hello, 🏴world!".to_string()),
+            Err(Error::Disallowed {
+                offset: 7, c: '🏴'
+            })
+        );
+        let s: SanitizedString = "Hello, world!".parse().unwrap();
+        assert!(s.is_owned());
+    }
+
     #[cfg(feature = "serde")]
     #[cfg(all(not(feature = "emoticons-emoji"), not(feature = "verbose")))]
     #[test]
@@ -193,6 +879,94 @@ mod tests {
         };
         let json = serde_json::to_string(&t).unwrap();
         assert_eq!(json, r#"{"s":"Hello, world!"}"#);
+
+        // `'static` can be deserialized from a temporary
+        let s: CowStr<'static> = serde_json::from_str(&json[5..json.len() - 1]).unwrap();
+        assert!(s.is_owned());
+        assert_eq!(s.as_ref(), "Hello, world!");
+    }
+
+    #[cfg(feature = "serde")]
+    #[cfg(all(not(feature = "emoticons-emoji"), not(feature = "verbose")))]
+    #[test]
+    fn test_serde_in_place() {
+        use serde::Deserialize;
+
+        let mut s = SanitizedString::from(String::with_capacity(64));
+        let ptr = s.as_ptr();
+        for json in [r#""Hello, world!""#, r#""Hello,\tworld!""#, "\"Hello🏴!\""] {
+            let mut de = serde_json::Deserializer::from_str(json);
+            SanitizedString::deserialize_in_place(&mut de, &mut s).unwrap();
+            // the buffer is reused
+            assert_eq!(s.as_ptr(), ptr);
+        }
+        assert_eq!(s.as_ref(), "Hello!");
+
+        let mut s = CowStr::from("borrowed");
+        let mut de = serde_json::Deserializer::from_str(r#""Hello, world!""#);
+        CowStr::deserialize_in_place(&mut de, &mut s).unwrap();
+        assert!(s.is_owned());
+        assert_eq!(s.as_ref(), "Hello, world!");
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        assert_eq!(schemars::schema_for!(CowStr), schemars::schema_for!(String));
+
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Request<'a> {
+            prompt: CowStr<'a>,
+        }
+        let schema = schemars::schema_for!(Request);
+        assert_eq!(
+            schema.get("properties").unwrap()["prompt"],
+            serde_json::json!({ "type": "string" })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_strict() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Test<'a> {
+            #[serde(deserialize_with = "CowStr::deserialize_strict")]
+            s: CowStr<'a>,
+        }
+
+        let t: Test = serde_json::from_str(r#"{"s":"Hello, world!"}"#).unwrap();
+        assert_eq!(t.s.as_ref(), "Hello, world!");
+        let err = serde_json::from_str::<Test>("{\"s\":\"Hello, 🏴world!\"}").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("disallowed character U+1F3F4 at byte 7"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[cfg(all(not(feature = "emoticons-emoji"), not(feature = "verbose")))]
+    #[test]
+    fn test_serde_borrowed() {
+        #[derive(serde::Deserialize)]
+        struct Test<'a> {
+            #[serde(borrow, deserialize_with = "CowStr::deserialize_borrowed")]
+            s: CowStr<'a>,
+        }
+
+        let json = r#"{"s":"Hello, world!"}"#;
+        let t: Test = serde_json::from_str(json).unwrap();
+        assert!(t.s.is_borrowed());
+        assert_eq!(t.s.as_ref(), "Hello, world!");
+
+        // Escapes and sanitization both require a copy
+        let t: Test = serde_json::from_str(r#"{"s":"Hello,\nworld!"}"#).unwrap();
+        assert!(t.s.is_owned());
+        assert_eq!(t.s.as_ref(), "Hello,\nworld!");
+        let t: Test = serde_json::from_str(r#"{"s":"Hello, world!\uD83D\uDE00"}"#).unwrap();
+        assert_eq!(t.s.as_ref(), "Hello, world!");
+        let t: Test = serde_json::from_str("{\"s\":\"Hello, 🏴world!\"}").unwrap();
+        assert!(t.s.is_owned());
+        assert_eq!(t.s.as_ref(), "Hello, world!");
     }
 
     #[test]
@@ -231,4 +1005,121 @@ mod tests {
             "Hello, [12 BYTES SANITIZED]world! That's all folks!"
         );
     }
+
+    #[test]
+    fn test_map() {
+        let s = CowStr::from("hello");
+        assert_eq!(s.map(str::to_uppercase).as_ref(), "HELLO");
+        assert!(s.map(|s| s.to_string()).is_owned());
+
+        // Disallowed characters the transform adds are removed
+        let s = s.map(|s| format!("{s}\u{1F3F4}{s}"));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(s.as_ref(), "hellohello");
+        #[cfg(feature = "verbose")]
+        assert_eq!(s.as_ref(), "hello[4 BYTES SANITIZED]hello");
+    }
+
+    #[test]
+    fn test_alternate() {
+        let quiet = Policy::new(&[0x20..=0x7E]).verbose(false);
+        crate::with_policy(quiet, || {
+            let mut s = CowStr::from("a🏴b");
+            assert_eq!(s.to_string(), "ab");
+            assert_eq!(format!("{s:#}"), "a[4 BYTES SANITIZED]b");
+            s.push_str("c\u{E0041}\u{E0042}");
+            assert_eq!(s.to_string(), "abc");
+            assert_eq!(
+                format!("{s:#}"),
+                "a[4 BYTES SANITIZED]bc[8 BYTES SANITIZED]"
+            );
+            assert_eq!(format!("{:#}", s.clone().into_static()), format!("{s:#}"));
+            // Only the strings are compared
+            assert_eq!(s, CowStr::from("abc"));
+            assert_eq!(format!("{:#}", CowStr::from("abc")), "abc");
+        });
+
+        // Marked text isn't marked twice
+        let verbose = Policy::new(&[0x20..=0x7E]).verbose(true);
+        crate::with_policy(verbose, || {
+            let s = CowStr::from("a🏴b");
+            assert_eq!(format!("{s:#}"), "a[4 BYTES SANITIZED]b");
+        });
+    }
+
+    #[test]
+    fn test_debug() {
+        let policy = Policy::new(&[0x20..=0x7E]);
+        crate::with_policy(policy, || {
+            assert_eq!(
+                format!("{:?}", CowStr::from("hi")),
+                r#"CowStr { value: "hi", modified: false, removed_bytes: 0, replaced: 0, blocks: {} }"#
+            );
+            let mut s = CowStr::from("a🏴b");
+            s.push_str("c\u{E0041}");
+            let debug = format!("{s:?}");
+            assert!(
+                debug.contains("modified: true, removed_bytes: 8"),
+                "{debug}"
+            );
+            assert!(
+                debug
+                    .contains(r#"blocks: {"Miscellaneous Symbols and Pictographs": 1, "Tags": 1}"#),
+                "{debug}"
+            );
+
+            let outcome = crate::sanitize_outcome("x\u{E0041}y");
+            let report = outcome.report().unwrap();
+            assert_eq!(report.removed_by_block()["Tags"], 1);
+            assert!(format!("{report:?}").contains("modified: true"));
+        });
+
+        use crate::ranges::block_name;
+        assert_eq!(block_name('a'), "Basic Latin");
+        assert_eq!(block_name('\u{FFFD}'), "Specials");
+        assert_eq!(block_name('\u{E0001}'), "Tags");
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut s = CowStr::from("a\u{E0041}b");
+        s.push_str("c🏴d");
+        assert_eq!(verify(&s), Ok(()));
+        // Something smuggled past sanitization
+        let smuggled = CowStr::clean(Cow::Borrowed("hi🏴"));
+        assert_eq!(
+            verify(&smuggled),
+            Err(Error::Disallowed {
+                offset: 2, c: '🏴'
+            })
+        );
+        let letters = |c: char| match c {
+            'a'..='z' => Decision::Allow,
+            _ => Decision::Remove,
+        };
+        assert!(verify_with(&CowStr::from("hi!"), &letters).is_err());
+        with_policy(Policy::new(&[0x61..=0x7A]), || {
+            assert!(verify(&CowStr::from("hi")).is_ok());
+        });
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut s = SanitizedString::with_capacity(16);
+        assert!(s.is_owned() && s.is_empty());
+        assert!(s.capacity() >= 16);
+        s.push_str("hi");
+        s.reserve(100);
+        assert!(s.capacity() >= 102);
+        s.shrink_to_fit();
+        assert_eq!(s.as_ref(), "hi");
+        assert!(s.capacity() < 102);
+
+        let mut borrowed = CowStr::from("hi");
+        assert_eq!(borrowed.capacity(), 0);
+        borrowed.shrink_to_fit();
+        assert!(borrowed.is_borrowed());
+        borrowed.reserve(8);
+        assert!(borrowed.is_owned() && borrowed.capacity() >= 10);
+    }
 }
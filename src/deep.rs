@@ -0,0 +1,423 @@
+/// Deep, in-place sanitization of nested types.
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use crate::{
+    batch::sanitize_keys,
+    policy::{with_current, with_named_policy},
+    san::sanitize_in_place,
+    sanitize_strict, Error,
+};
+
+/// Types whose strings can be sanitized in place, such as messages generated
+/// by `prost`. Implement it for your own structs with [`impl_sanitize!`], or
+/// with `#[derive(Sanitize)]` and the `derive` feature, which can sanitize
+/// fields with policies given to [`register_policy`] by name:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use langsan::{register_policy, Policy, Sanitize};
+///
+/// #[derive(Sanitize)]
+/// struct Snippet {
+///     #[langsan(policy = "code")]
+///     code: String,
+///     #[langsan(policy = "chat_input")]
+///     comment: String,
+///     #[langsan(skip)]
+///     line: u32,
+/// }
+///
+/// register_policy("code", Policy::default());
+/// register_policy("chat_input", Policy::default().forbid(['<', '>']));
+/// let mut snippet = Snippet {
+///     code: "Vec<u8>".into(),
+///     comment: "bytes<".into(),
+///     line: 1,
+/// };
+/// assert_eq!(snippet.sanitize(), 1);
+/// assert_eq!(snippet.code, "Vec<u8>");
+/// # }
+/// ```
+///
+/// [`impl_sanitize!`]: crate::impl_sanitize
+/// [`register_policy`]: crate::register_policy
+pub trait Sanitize {
+    /// Sanitize every string in `self` in place with the active policy.
+    /// Returns the number of strings modified.
+    ///
+    /// # Panics
+    ///
+    /// Implementations from `#[derive(Sanitize)]` panic if a field's named
+    /// policy isn't registered. Use [`Sanitize::try_sanitize`] to handle that.
+    fn sanitize(&mut self) -> usize;
+
+    /// Strict [`Sanitize::sanitize`]. Returns an [`Error::Disallowed`] for
+    /// the first string sanitization would modify, without modifying anything.
+    fn sanitize_strict(&self) -> Result<(), Error>;
+
+    /// Fallible [`Sanitize::sanitize`], returning an [`Error::InvalidPolicy`]
+    /// rather than panicking if a field's named policy isn't registered. The
+    /// fields before it may already have been sanitized.
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        Ok(self.sanitize())
+    }
+}
+
+impl Sanitize for String {
+    fn sanitize(&mut self) -> usize {
+        with_current(|policy| sanitize_in_place(self, policy, &mut String::new()) as usize)
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        sanitize_strict(self).map(|_| ())
+    }
+}
+
+impl<T: Sanitize + ?Sized> Sanitize for Box<T> {
+    fn sanitize(&mut self) -> usize {
+        (**self).sanitize()
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        (**self).sanitize_strict()
+    }
+
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        (**self).try_sanitize()
+    }
+}
+
+impl<T: Sanitize> Sanitize for Option<T> {
+    fn sanitize(&mut self) -> usize {
+        self.as_mut().map_or(0, Sanitize::sanitize)
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        self.as_ref().map_or(Ok(()), Sanitize::sanitize_strict)
+    }
+
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        self.as_mut().map_or(Ok(0), Sanitize::try_sanitize)
+    }
+}
+
+impl<T: Sanitize> Sanitize for [T] {
+    fn sanitize(&mut self) -> usize {
+        self.iter_mut().map(Sanitize::sanitize).sum()
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        self.iter().try_for_each(Sanitize::sanitize_strict)
+    }
+
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        self.iter_mut().map(Sanitize::try_sanitize).sum()
+    }
+}
+
+impl<T: Sanitize> Sanitize for Vec<T> {
+    fn sanitize(&mut self) -> usize {
+        self.as_mut_slice().sanitize()
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        self.as_slice().sanitize_strict()
+    }
+
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        self.as_mut_slice().try_sanitize()
+    }
+}
+
+/// Like [`sanitize_hash_map`], an entry whose sanitized key is already present
+/// is dropped, and of several modified keys sanitizing to the same key, the
+/// least is kept.
+///
+/// [`sanitize_hash_map`]: crate::sanitize_hash_map
+impl<V, S> Sanitize for HashMap<String, V, S>
+where
+    V: Sanitize,
+    S: BuildHasher,
+{
+    fn sanitize(&mut self) -> usize {
+        let modified: usize = self.values_mut().map(Sanitize::sanitize).sum();
+        modified + with_current(|policy| sanitize_keys(self, policy, &mut String::new()))
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        map_strict(self.iter())
+    }
+
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        let modified: usize = self
+            .values_mut()
+            .map(Sanitize::try_sanitize)
+            .sum::<Result<_, _>>()?;
+        Ok(modified + with_current(|policy| sanitize_keys(self, policy, &mut String::new())))
+    }
+}
+
+/// As for a [`HashMap`], an entry whose sanitized key is already present is
+/// dropped, and of several modified keys sanitizing to the same key, the least
+/// is kept.
+impl<V> Sanitize for BTreeMap<String, V>
+where
+    V: Sanitize,
+{
+    fn sanitize(&mut self) -> usize {
+        let modified: usize = self.values_mut().map(Sanitize::sanitize).sum();
+        modified + with_current(|policy| sanitize_keys(self, policy, &mut String::new()))
+    }
+
+    fn sanitize_strict(&self) -> Result<(), Error> {
+        map_strict(self.iter())
+    }
+
+    fn try_sanitize(&mut self) -> Result<usize, Error> {
+        let modified: usize = self
+            .values_mut()
+            .map(Sanitize::try_sanitize)
+            .sum::<Result<_, _>>()?;
+        Ok(modified + with_current(|policy| sanitize_keys(self, policy, &mut String::new())))
+    }
+}
+
+/// Strict sanitization of map entries.
+fn map_strict<'a, V>(entries: impl Iterator<Item = (&'a String, &'a V)>) -> Result<(), Error>
+where
+    V: Sanitize + 'a,
+{
+    for (key, value) in entries {
+        key.sanitize_strict()?;
+        value.sanitize_strict()?;
+    }
+    Ok(())
+}
+
+/// Implement [`Sanitize`] for structs by listing their string-bearing fields,
+/// which must themselves implement [`Sanitize`]. This is intended for types
+/// generated by `prost` and the like, which can't easily be derived:
+///
+/// ```
+/// struct Message {
+///     role: String,
+///     content: Vec<String>,
+///     id: u64,
+/// }
+///
+/// langsan::impl_sanitize!(Message { role, content });
+///
+/// use langsan::Sanitize;
+/// let mut message = Message {
+///     role: "user".into(),
+///     content: vec!["hi🏴".into()],
+///     id: 7,
+/// };
+/// assert_eq!(message.sanitize(), 1);
+/// assert!(message.sanitize_strict().is_ok());
+/// ```
+///
+/// A field may be followed by a [`Policy`] to use for it instead of the active
+/// one, since a struct often mixes code, URLs and natural language. The
+/// expression is evaluated on every call, so a policy that's costly to build
+/// is better registered by name and used with `#[derive(Sanitize)]`:
+///
+/// ```
+/// use langsan::{Policy, Sanitize};
+///
+/// struct Snippet {
+///     code: String,
+///     comment: String,
+/// }
+///
+/// langsan::impl_sanitize!(Snippet {
+///     code,
+///     comment: Policy::default().forbid(['<', '>']),
+/// });
+///
+/// let mut snippet = Snippet {
+///     code: "Vec<u8>".into(),
+///     comment: "bytes<".into(),
+/// };
+/// assert_eq!(snippet.sanitize(), 1);
+/// assert_eq!(snippet.code, "Vec<u8>");
+/// ```
+///
+/// [`Sanitize`]: crate::Sanitize
+/// [`Policy`]: crate::Policy
+#[macro_export]
+macro_rules! impl_sanitize {
+    ($($ty:ty { $($field:ident $(: $policy:expr)?),* $(,)? }),+ $(,)?) => {
+        $(
+            impl $crate::Sanitize for $ty {
+                fn sanitize(&mut self) -> usize {
+                    0 $(+ $crate::__sanitize_field!(sanitize, &mut self.$field $(, $policy)?))*
+                }
+
+                fn sanitize_strict(&self) -> ::std::result::Result<(), $crate::Error> {
+                    $($crate::__sanitize_field!(sanitize_strict, &self.$field $(, $policy)?)?;)*
+                    Ok(())
+                }
+
+                fn try_sanitize(&mut self) -> ::std::result::Result<usize, $crate::Error> {
+                    Ok(0 $(+ $crate::__sanitize_field!(try_sanitize, &mut self.$field $(, $policy)?)?)*)
+                }
+            }
+        )+
+    };
+}
+
+/// [`Sanitize::sanitize`] `field` with the policy registered as `name`, for
+/// `#[derive(Sanitize)]`. Panics if there isn't one.
+#[doc(hidden)]
+#[track_caller]
+pub fn __sanitize_named<T>(name: &str, field: &mut T) -> usize
+where
+    T: Sanitize + ?Sized,
+{
+    match with_named_policy(name, || field.sanitize()) {
+        Ok(modified) => modified,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+/// [`Sanitize::try_sanitize`] `field` with the policy registered as `name`,
+/// for `#[derive(Sanitize)]`.
+#[doc(hidden)]
+pub fn __try_sanitize_named<T>(name: &str, field: &mut T) -> Result<usize, Error>
+where
+    T: Sanitize + ?Sized,
+{
+    with_named_policy(name, || field.try_sanitize())?
+}
+
+/// [`Sanitize::sanitize_strict`] `field` with the policy registered as
+/// `name`, for `#[derive(Sanitize)]`.
+#[doc(hidden)]
+pub fn __sanitize_strict_named<T>(name: &str, field: &T) -> Result<(), Error>
+where
+    T: Sanitize + ?Sized,
+{
+    with_named_policy(name, || field.sanitize_strict())?
+}
+
+/// Call a [`Sanitize`] `method` on a `field`, with its `policy` if it has one.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sanitize_field {
+    ($method:ident, $field:expr) => {
+        $crate::Sanitize::$method($field)
+    };
+    ($method:ident, $field:expr, $policy:expr) => {
+        $crate::with_policy($policy, || $crate::Sanitize::$method($field))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{with_policy, Policy};
+
+    #[derive(Debug)]
+    struct Message {
+        role: String,
+        content: Option<String>,
+    }
+
+    #[derive(Debug)]
+    struct Request {
+        model: String,
+        messages: Vec<Message>,
+        metadata: HashMap<String, String>,
+        tags: BTreeMap<String, Option<String>>,
+        n: i32,
+    }
+
+    crate::impl_sanitize!(
+        Message { role, content },
+        Request {
+            model,
+            messages,
+            metadata,
+            tags,
+        },
+    );
+
+    /// A snippet whose comment may not contain markup.
+    struct Snippet {
+        code: String,
+        comment: Option<String>,
+    }
+
+    crate::impl_sanitize!(Snippet {
+        code,
+        comment: Policy::default().forbid(['<', '>']),
+    });
+
+    #[test]
+    fn test_field_policies() {
+        let mut snippet = Snippet {
+            code: "Vec<u8>".into(),
+            comment: Some("a <b>".into()),
+        };
+        assert_eq!(
+            snippet.sanitize_strict(),
+            Err(Error::Disallowed { offset: 2, c: '<' })
+        );
+        assert_eq!(snippet.sanitize(), 1);
+        assert_eq!(snippet.sanitize_strict(), Ok(()));
+        assert_eq!(snippet.code, "Vec<u8>");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(snippet.comment.as_deref(), Some("a "));
+
+        // The field's policy applies whatever the active one is
+        snippet.comment = Some("<".into());
+        let permissive = Policy::deny([]);
+        assert!(with_policy(permissive.clone(), || snippet.sanitize_strict()).is_err());
+        assert_eq!(with_policy(permissive, || snippet.try_sanitize()), Ok(1));
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let mut request = Request {
+            model: "gpt".into(),
+            messages: vec![
+                Message {
+                    role: "user".into(),
+                    content: Some("hi🏴".into()),
+                },
+                Message {
+                    role: "user🏴".into(),
+                    content: None,
+                },
+            ],
+            metadata: [("k🏴".into(), "v🏴".into()), ("k".into(), "clean".into())].into(),
+            tags: [("t🏴".into(), Some("x".into()))].into(),
+            n: 1,
+        };
+        assert_eq!(
+            request.sanitize_strict(),
+            Err(Error::Disallowed {
+                offset: 2, c: '🏴'
+            })
+        );
+
+        assert_eq!(request.sanitize(), 5);
+        assert_eq!(request.sanitize_strict(), Ok(()));
+        assert_eq!(request.sanitize(), 0);
+        assert_eq!(request.try_sanitize(), Ok(0));
+        assert_eq!(request.n, 1);
+        #[cfg(not(feature = "verbose"))]
+        {
+            // The clean key wins
+            assert_eq!(request.metadata.len(), 1);
+            assert_eq!(request.messages[0].content.as_deref(), Some("hi"));
+            assert_eq!(request.metadata["k"], "clean");
+            assert_eq!(request.tags["t"].as_deref(), Some("x"));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+/// A case-insensitive denylist of exact phrases.
+use std::{borrow::Cow, ops::Range};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use crate::Error;
+
+/// A fast, case-insensitive list of phrases to block or flag in input or
+/// output, such as `ignore previous instructions`. Unlike sanitization, this
+/// never modifies the text.
+///
+/// ```
+/// use langsan::Denylist;
+///
+/// let denylist = Denylist::new(["ignore previous instructions", "sudo"]).unwrap();
+/// let matches: Vec<_> = denylist.find_iter("Please IGNORE previous instructions").collect();
+/// assert_eq!(matches[0].phrase(), "ignore previous instructions");
+/// assert_eq!(matches[0].range(), 7..35);
+/// assert!(denylist.check("hello").is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Denylist {
+    phrases: Vec<String>,
+    searcher: AhoCorasick,
+}
+
+/// A denied phrase found in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenylistMatch<'a> {
+    phrase: &'a str,
+    range: Range<usize>,
+}
+
+impl DenylistMatch<'_> {
+    /// The phrase as given to the [`Denylist`].
+    pub fn phrase(&self) -> &str {
+        self.phrase
+    }
+
+    /// The byte range of the input that matched.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl Denylist {
+    /// Create a denylist of `phrases`. Matching folds case, so the phrases'
+    /// case doesn't matter. Returns an [`Error::InvalidPolicy`] if there are
+    /// too many phrases to search for.
+    pub fn new<I, P>(phrases: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        let phrases: Vec<String> = phrases.into_iter().map(Into::into).collect();
+        let searcher = AhoCorasick::builder()
+            .ascii_case_insensitive(true)
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(phrases.iter().map(|p| fold(p).0.into_owned()))
+            .map_err(|e| Error::InvalidPolicy(e.to_string()))?;
+        Ok(Self { phrases, searcher })
+    }
+
+    /// The denied phrases in `s`, in order. Overlapping matches are not
+    /// reported, and the longest phrase is preferred.
+    pub fn find_iter<'a>(&'a self, s: &str) -> impl Iterator<Item = DenylistMatch<'a>> {
+        // Non-ASCII input is searched case folded, mapping back to `s`.
+        let (folded, offsets) = fold(s);
+        let matches: Vec<_> = self.searcher.find_iter(folded.as_ref()).collect();
+        matches.into_iter().map(move |m| {
+            let range = match &offsets {
+                Some(offsets) => offsets[m.start()]..offsets[m.end()],
+                None => m.range(),
+            };
+            DenylistMatch {
+                phrase: &self.phrases[m.pattern()],
+                range,
+            }
+        })
+    }
+
+    /// Returns true if `s` contains any denied phrase.
+    pub fn is_match(&self, s: &str) -> bool {
+        self.find_iter(s).next().is_some()
+    }
+
+    /// Returns an [`Error::Denied`] for the first denied phrase in `s`.
+    pub fn check(&self, s: &str) -> Result<(), Error> {
+        match self.find_iter(s).next() {
+            Some(m) => Err(Error::Denied {
+                offset: m.range.start,
+                phrase: m.phrase.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Lowercase `s` if it isn't ASCII, which the searcher handles itself. The
+/// offsets map each byte of the result, and its end, to the start and end of
+/// the character in `s` it came from.
+fn fold(s: &str) -> (Cow<'_, str>, Option<Vec<usize>>) {
+    if s.is_ascii() {
+        return (s.into(), None);
+    }
+    let mut folded = String::with_capacity(s.len());
+    let mut offsets = Vec::with_capacity(s.len() + 1);
+    for (i, c) in s.char_indices() {
+        let start = folded.len();
+        folded.extend(c.to_lowercase());
+        offsets.push(i);
+        // Bytes after the first map to the end of the character, so a match
+        // ending inside it includes all of it.
+        offsets.extend(std::iter::repeat_n(
+            i + c.len_utf8(),
+            folded.len() - start - 1,
+        ));
+    }
+    offsets.push(s.len());
+    (folded.into(), Some(offsets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denylist() {
+        let denylist =
+            Denylist::new(["Ignore previous", "ignore previous instructions", "ÜBER"]).unwrap();
+        assert!(!denylist.is_match("nothing here"));
+        assert_eq!(denylist.check("nothing here"), Ok(()));
+
+        // The longest match wins, whatever the case
+        let matches: Vec<_> = denylist
+            .find_iter("ignore PREVIOUS instructions, über alles")
+            .collect();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].phrase(), "ignore previous instructions");
+        assert_eq!(matches[0].range(), 0..28);
+        assert_eq!(matches[1].phrase(), "ÜBER");
+        assert_eq!(matches[1].range(), 30..35);
+
+        // Offsets are into the original input, even if folding changes it
+        let s = "İİ Über";
+        let m = denylist.find_iter(s).next().unwrap();
+        assert_eq!(&s[m.range()], "Über");
+
+        assert_eq!(
+            denylist.check("so: Ignore Previous"),
+            Err(Error::Denied {
+                offset: 4,
+                phrase: "Ignore previous".into()
+            })
+        );
+    }
+}
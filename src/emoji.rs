@@ -0,0 +1,232 @@
+//! Emoji sequence validation for the `emoji-full` feature, which allows the
+//! zero width joiner, variation selectors and keycap mark only where they
+//! form part of an emoji. Anywhere else they are invisible and could smuggle
+//! content, so they are removed.
+//!
+//! Pictographs are recognized by their Unicode emoji properties, generated
+//! from `emoji-data.txt`.
+use std::ops::RangeInclusive;
+
+include!(concat!(env!("OUT_DIR"), "/emoji.rs"));
+
+/// Zero width joiner, joining emoji into one glyph.
+const ZWJ: char = '\u{200D}';
+/// Combining enclosing keycap, as in 1️⃣.
+const KEYCAP: char = '\u{20E3}';
+/// Text presentation selector.
+const VS15: char = '\u{FE0E}';
+/// Emoji presentation selector.
+const VS16: char = '\u{FE0F}';
+
+/// Returns true if `c` is only valid within an emoji sequence.
+pub(crate) const fn is_joiner(c: char) -> bool {
+    matches!(c, ZWJ | KEYCAP | VS15 | VS16)
+}
+
+/// How a character takes part in emoji, from [`classify_emoji`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmojiKind {
+    /// A pictograph shown as emoji by default, such as 😀.
+    Emoji,
+    /// A pictograph shown as text unless followed by the emoji presentation
+    /// selector, such as © or ❤.
+    Text,
+    /// Part of an emoji that isn't a pictograph itself: a skin tone modifier,
+    /// a regional indicator of a flag, a zero width joiner, a variation
+    /// selector or the keycap mark.
+    Component,
+}
+
+/// Classify `c` by its Unicode emoji properties rather than its block, since
+/// pictographs are spread over many blocks, several of which also hold plain
+/// symbols. Pictographs are characters with the `Extended_Pictographic`
+/// property, which includes those not yet assigned as emoji. Returns `None`
+/// for anything else, including keycap bases such as `1`.
+///
+/// ```
+/// use langsan::{classify_emoji, EmojiKind};
+///
+/// assert_eq!(classify_emoji('😀'), Some(EmojiKind::Emoji));
+/// assert_eq!(classify_emoji('©'), Some(EmojiKind::Text));
+/// assert_eq!(classify_emoji('\u{1F3FD}'), Some(EmojiKind::Component));
+/// // In the same block as ⌚, but not a pictograph
+/// assert_eq!(classify_emoji('⌘'), None);
+/// ```
+pub const fn classify_emoji(c: char) -> Option<EmojiKind> {
+    if in_table(EXTENDED_PICTOGRAPHIC, c) {
+        if in_table(EMOJI_PRESENTATION, c) {
+            Some(EmojiKind::Emoji)
+        } else {
+            Some(EmojiKind::Text)
+        }
+    } else if in_table(EMOJI_PRESENTATION, c) || in_table(EMOJI_MODIFIER, c) || is_joiner(c) {
+        Some(EmojiKind::Component)
+    } else {
+        None
+    }
+}
+
+/// Returns true if `c` is in the sorted `table`.
+const fn in_table(table: &[RangeInclusive<u32>], c: char) -> bool {
+    let c = c as u32;
+    let (mut low, mut high) = (0, table.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if c < *table[mid].start() {
+            high = mid;
+        } else if c > *table[mid].end() {
+            low = mid + 1;
+        } else {
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns true if `c` can start or continue an emoji sequence.
+const fn is_pictographic(c: char) -> bool {
+    in_table(EXTENDED_PICTOGRAPHIC, c) || in_table(EMOJI_MODIFIER, c)
+}
+
+/// Returns true if `c` can precede a keycap.
+const fn is_keycap_base(c: char) -> bool {
+    c.is_ascii_digit() || c == '#' || c == '*'
+}
+
+/// Returns true if the joiner `c` at byte `i` of `s` is part of a valid emoji
+/// sequence of characters that are themselves `allowed`:
+///
+/// * A variation selector directly follows a pictograph or keycap base.
+/// * A keycap follows a keycap base, optionally with an emoji selector.
+/// * A zero width joiner is between pictographs, the first of which may be
+///   followed by a selector or skin tone modifier.
+pub(crate) fn in_sequence(s: &str, i: usize, c: char, allowed: impl Fn(char) -> bool) -> bool {
+    let is_pictographic = |c| is_pictographic(c) && allowed(c);
+    let is_keycap_base = |c| is_keycap_base(c) && allowed(c);
+    let mut before = s[..i].chars().rev();
+    let prev = before.next();
+    match c {
+        VS15 | VS16 => prev.is_some_and(|p| is_pictographic(p) || is_keycap_base(p)),
+        KEYCAP => match prev {
+            Some(VS16) => before.next().is_some_and(is_keycap_base),
+            Some(p) => is_keycap_base(p),
+            None => false,
+        },
+        ZWJ => {
+            let next = s[i + c.len_utf8()..].chars().next();
+            // Pictographs include the skin tone modifiers
+            let prev = match prev {
+                Some(VS16) => before.next(),
+                prev => prev,
+            };
+            prev.is_some_and(is_pictographic) && next.is_some_and(is_pictographic)
+        }
+        _ => true,
+    }
+}
+
+/// Returns true if `s`, cut from sanitized text, begins or ends partway through
+/// an emoji sequence, leaving a joiner without what it joins. Only a leading
+/// joiner or a trailing zero width joiner can be cut off.
+pub(crate) fn cuts_sequence(s: &str) -> bool {
+    s.starts_with(is_joiner) || s.ends_with(ZWJ)
+}
+
+/// `s`, cut from sanitized text, without any joiners [`cuts_sequence`] would
+/// find.
+pub(crate) fn trim_cut(s: &str) -> &str {
+    s.trim_start_matches(is_joiner).trim_end_matches(ZWJ)
+}
+
+/// A `const` [`in_sequence`] for string literals, given the two characters
+/// before the joiner `c` and the one after. Whether they are allowed must be
+/// checked separately.
+pub(crate) const fn in_literal_sequence(
+    c: char,
+    prev: Option<char>,
+    prev2: Option<char>,
+    next: Option<char>,
+) -> bool {
+    const fn is_pictographic_at(c: Option<char>) -> bool {
+        matches!(c, Some(c) if is_pictographic(c))
+    }
+    const fn is_keycap_base_at(c: Option<char>) -> bool {
+        matches!(c, Some(c) if is_keycap_base(c))
+    }
+    match c {
+        VS15 | VS16 => is_pictographic_at(prev) || is_keycap_base_at(prev),
+        KEYCAP => match prev {
+            Some(VS16) => is_keycap_base_at(prev2),
+            prev => is_keycap_base_at(prev),
+        },
+        ZWJ => {
+            let prev = match prev {
+                Some(VS16) => prev2,
+                prev => prev,
+            };
+            is_pictographic_at(prev) && is_pictographic_at(next)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check every joiner in `s` is in a valid sequence of anything but 🏴.
+    fn valid(s: &str) -> bool {
+        s.char_indices()
+            .filter(|(_, c)| is_joiner(*c))
+            .all(|(i, c)| in_sequence(s, i, c, |c| c != '🏴'))
+    }
+
+    #[test]
+    fn test_in_sequence() {
+        // Family, rainbow flag, skin tones, gendered roles, keycaps, hearts
+        assert!(valid("👨‍👩‍👧"));
+        assert!(valid("🏳️‍🌈"));
+        assert!(valid("👋🏽 🧑🏽‍💻"));
+        assert!(valid("🏃‍♀️"));
+        assert!(valid("1️⃣ #⃣"));
+        assert!(valid("❤️ ❤︎"));
+
+        // Joiners outside of emoji are removed
+        assert!(!valid("a\u{200D}b"));
+        assert!(!valid("👨\u{200D}"));
+        assert!(!valid("\u{200D}👩"));
+        assert!(!valid("👨\u{200D}\u{200D}👩"));
+        assert!(!valid("a\u{FE0F}"));
+        assert!(!valid("❤\u{FE0F}\u{FE0F}"));
+        assert!(!valid("\u{FE0F}"));
+        assert!(!valid("a\u{20E3}"));
+        assert!(!valid("\u{20E3}"));
+        // As are those joined to a character that will be removed
+        assert!(!valid("🏴\u{FE0F}"));
+        assert!(!valid("🏴\u{200D}👩"));
+        assert!(!valid("👩\u{200D}🏴"));
+        // Arrows are in the same block as pictographs, but aren't one
+        assert!(!valid("→\u{FE0F}"));
+        assert!(valid("↔\u{FE0F}"));
+    }
+
+    #[test]
+    fn test_classify_emoji() {
+        assert_eq!(classify_emoji('🏴'), Some(EmojiKind::Emoji));
+        // Outside the emoji blocks
+        assert_eq!(classify_emoji('⌚'), Some(EmojiKind::Emoji));
+        assert_eq!(classify_emoji('™'), Some(EmojiKind::Text));
+        assert_eq!(classify_emoji('❤'), Some(EmojiKind::Text));
+        // Unassigned, but reserved for pictographs
+        assert_eq!(classify_emoji('\u{1FAFF}'), Some(EmojiKind::Text));
+        for c in ['\u{1F1FA}', '\u{1F3FB}', ZWJ, KEYCAP, VS15, VS16] {
+            assert_eq!(classify_emoji(c), Some(EmojiKind::Component), "{c:?}");
+        }
+        for c in ['a', '1', '#', '→', '⌘', '⚆', '\u{1F10C}', '\u{E0041}'] {
+            assert_eq!(classify_emoji(c), None, "{c:?}");
+        }
+        assert!(EXTENDED_PICTOGRAPHIC
+            .windows(2)
+            .all(|w| w[0].end() + 1 < *w[1].start()));
+    }
+}
@@ -0,0 +1,66 @@
+/// Errors returned by fallible APIs.
+use std::fmt;
+
+/// An error returned by the fallible, strict or limited, APIs in this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Strict mode: the input would have been modified by sanitization because
+    /// of the character `c` at byte `offset`.
+    Disallowed { offset: usize, c: char },
+    /// The input is `len` bytes, more than the `max` allowed.
+    TooLong { len: usize, max: usize },
+    /// A policy could not be loaded or parsed.
+    InvalidPolicy(String),
+    /// A value could not be serialized or deserialized.
+    Serde(String),
+    /// The input contains the denied `phrase` at byte `offset`.
+    Denied { offset: usize, phrase: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Disallowed { offset, c } => write!(
+                f,
+                "disallowed character U+{:04X} at byte {}",
+                *c as u32, offset
+            ),
+            Error::TooLong { len, max } => {
+                write!(f, "input of {} bytes exceeds the limit of {}", len, max)
+            }
+            Error::InvalidPolicy(reason) => write!(f, "invalid policy: {}", reason),
+            Error::Serde(reason) => write!(f, "serde error: {}", reason),
+            Error::Denied { offset, phrase } => {
+                write!(f, "denied phrase {:?} at byte {}", phrase, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = Error::Disallowed {
+            offset: 7,
+            c: '\u{E0041}',
+        };
+        assert_eq!(err.to_string(), "disallowed character U+E0041 at byte 7");
+        let err = Error::TooLong { len: 10, max: 5 };
+        assert_eq!(err.to_string(), "input of 10 bytes exceeds the limit of 5");
+        let err: Box<dyn std::error::Error> = Error::InvalidPolicy("nope".into()).into();
+        assert_eq!(err.to_string(), "invalid policy: nope");
+        let err = Error::Serde("bad".into());
+        assert_eq!(err.to_string(), "serde error: bad");
+        let err = Error::Denied {
+            offset: 3,
+            phrase: "sudo".into(),
+        };
+        assert_eq!(err.to_string(), "denied phrase \"sudo\" at byte 3");
+    }
+}
@@ -0,0 +1,326 @@
+//! C bindings. Signatures are [cbindgen] compatible, so a header can be
+//! generated with `cbindgen --crate langsan --lang c`.
+//!
+//! The crate is only built as an `rlib`, so Cargo won't produce a C library
+//! with `cargo build`. Override the crate type with `cargo rustc` instead:
+//!
+//! ```text
+//! cargo rustc --release --lib --features ffi --crate-type staticlib
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! which write `liblangsan.a` or `liblangsan.so` (`.dylib`, `langsan.dll`)
+//! to `target/release`.
+//!
+//! Strings are passed as a pointer and a length in bytes, must be valid UTF-8,
+//! and need not be NUL-terminated. Returned strings are NUL-terminated for
+//! convenience, but may be freed only with [`langsan_string_free`].
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+use std::{os::raw::c_char, slice};
+
+use crate::{
+    policy::with_current,
+    san::{check, sanitize_outcome_with},
+    Error, Policy,
+};
+
+/// An opaque sanitization policy.
+pub struct LangsanPolicy(Policy);
+
+/// An inclusive range of Unicode code points.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LangsanRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Borrow `len` bytes at `ptr` as a `&str`, or `None` if `ptr` is NULL or the
+/// bytes aren't UTF-8.
+///
+/// # Safety
+/// `ptr` must be NULL or valid for reads of `len` bytes for `'a`.
+unsafe fn as_str<'a>(ptr: *const c_char, len: usize) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    std::str::from_utf8(slice::from_raw_parts(ptr.cast(), len)).ok()
+}
+
+/// Create a policy allowing only the `count` `ranges`. Known-problematic
+/// characters are still forbidden. Returns NULL if `ranges` is NULL with a
+/// nonzero `count` or any range is invalid. Free the policy with
+/// [`langsan_policy_free`].
+///
+/// # Safety
+/// `ranges` must be NULL or valid for reads of `count` ranges.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_policy_new(
+    ranges: *const LangsanRange,
+    count: usize,
+) -> *mut LangsanPolicy {
+    let ranges = match (ranges.is_null(), count) {
+        (_, 0) => &[][..],
+        (true, _) => return std::ptr::null_mut(),
+        (false, _) => slice::from_raw_parts(ranges, count),
+    };
+    if ranges
+        .iter()
+        .any(|r| r.start > r.end || r.end > char::MAX as u32)
+    {
+        return std::ptr::null_mut();
+    }
+    let ranges: Vec<_> = ranges.iter().map(|r| r.start..=r.end).collect();
+    Box::into_raw(Box::new(LangsanPolicy(Policy::new(ranges))))
+}
+
+/// Create a copy of the default policy. Free it with [`langsan_policy_free`].
+#[no_mangle]
+pub extern "C" fn langsan_policy_default() -> *mut LangsanPolicy {
+    Box::into_raw(Box::new(LangsanPolicy(Policy::default())))
+}
+
+/// Additionally forbid the `count` code points in `chars`, even if they are
+/// otherwise allowed. Invalid code points are ignored. Returns false if
+/// `policy` is NULL or `chars` is NULL with a nonzero `count`.
+///
+/// # Safety
+/// `policy` must be NULL or a live policy from this library. `chars` must be
+/// NULL or valid for reads of `count` code points.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_policy_forbid(
+    policy: *mut LangsanPolicy,
+    chars: *const u32,
+    count: usize,
+) -> bool {
+    let Some(policy) = policy.as_mut() else {
+        return false;
+    };
+    let chars = match (chars.is_null(), count) {
+        (_, 0) => &[][..],
+        (true, _) => return false,
+        (false, _) => slice::from_raw_parts(chars, count),
+    };
+    let inner = std::mem::take(&mut policy.0);
+    policy.0 = inner.forbid(chars.iter().filter_map(|&c| char::from_u32(c)));
+    true
+}
+
+/// Returns true if the code point `c` survives sanitization unchanged under
+/// `policy`, or the active policy if `policy` is NULL.
+///
+/// # Safety
+/// `policy` must be NULL or a live policy from this library.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_is_allowed(policy: *const LangsanPolicy, c: u32) -> bool {
+    let Some(c) = char::from_u32(c) else {
+        return false;
+    };
+    match policy.as_ref() {
+        Some(policy) => policy.0.is_allowed(c),
+        None => with_current(|policy| policy.is_allowed(c)),
+    }
+}
+
+/// Free a policy. Does nothing if `policy` is NULL.
+///
+/// # Safety
+/// `policy` must be NULL or a live policy from this library, which must not
+/// be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_policy_free(policy: *mut LangsanPolicy) {
+    if !policy.is_null() {
+        drop(Box::from_raw(policy));
+    }
+}
+
+/// Sanitize the `len` bytes of UTF-8 at `input` with `policy`, or the active
+/// policy if `policy` is NULL. Returns a newly allocated string, even if
+/// nothing was removed, and writes its length excluding the NUL terminator to
+/// `out_len`. Returns NULL if `input` or `out_len` is NULL or `input` is not
+/// UTF-8. Free the string with [`langsan_string_free`].
+///
+/// # Safety
+/// `policy` must be NULL or a live policy from this library. `input` must be
+/// NULL or valid for reads of `len` bytes. `out_len` must be NULL or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_sanitize(
+    policy: *const LangsanPolicy,
+    input: *const c_char,
+    len: usize,
+    out_len: *mut usize,
+) -> *mut c_char {
+    let (Some(input), Some(out_len)) = (as_str(input, len), out_len.as_mut()) else {
+        return std::ptr::null_mut();
+    };
+    let mut output = match policy.as_ref() {
+        Some(policy) => sanitize_outcome_with(input, &policy.0).into_cow(),
+        None => with_current(|policy| sanitize_outcome_with(input, policy).into_cow()),
+    }
+    .into_owned()
+    .into_bytes();
+    *out_len = output.len();
+    output.push(0);
+    Box::into_raw(output.into_boxed_slice()).cast()
+}
+
+/// Returns true if sanitizing the `len` bytes of UTF-8 at `input` with
+/// `policy`, or the active policy if `policy` is NULL, would not modify it.
+/// Otherwise, the byte offset of the first offending character, or of the
+/// first invalid UTF-8, is written to `offset` if it isn't NULL.
+///
+/// # Safety
+/// `policy` must be NULL or a live policy from this library. `input` must be
+/// NULL or valid for reads of `len` bytes. `offset` must be NULL or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_is_clean(
+    policy: *const LangsanPolicy,
+    input: *const c_char,
+    len: usize,
+    offset: *mut usize,
+) -> bool {
+    if input.is_null() {
+        return len == 0;
+    }
+    let bytes = slice::from_raw_parts(input.cast::<u8>(), len);
+    let (input, invalid) = match std::str::from_utf8(bytes) {
+        Ok(s) => (s, None),
+        Err(e) => (
+            std::str::from_utf8_unchecked(&bytes[..e.valid_up_to()]),
+            Some(e.valid_up_to()),
+        ),
+    };
+    let checked = match policy.as_ref() {
+        Some(policy) => check(input, &policy.0),
+        None => with_current(|policy| check(input, policy)),
+    };
+    let first = match checked {
+        Ok(()) => invalid,
+        Err(Error::Disallowed { offset, .. }) => Some(offset),
+        Err(_) => Some(0),
+    };
+    match (first, offset.as_mut()) {
+        (None, _) => true,
+        (Some(first), Some(offset)) => {
+            *offset = first;
+            false
+        }
+        (Some(_), None) => false,
+    }
+}
+
+/// Free a string returned by this library. `len` is the length written to
+/// `out_len` when it was returned. Does nothing if `s` is NULL.
+///
+/// # Safety
+/// `s` must be NULL or a live string from this library with length `len`,
+/// which must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn langsan_string_free(s: *mut c_char, len: usize) {
+    if !s.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            s.cast::<u8>(),
+            len + 1,
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::{null, null_mut};
+
+    use super::*;
+
+    /// Sanitize `s` through the C interface.
+    fn sanitize(policy: *const LangsanPolicy, s: &str) -> String {
+        unsafe {
+            let mut len = 0;
+            let out = langsan_sanitize(policy, s.as_ptr().cast(), s.len(), &mut len);
+            assert!(!out.is_null());
+            assert_eq!(*out.add(len), 0);
+            let sanitized = std::str::from_utf8(slice::from_raw_parts(out.cast(), len))
+                .unwrap()
+                .to_string();
+            langsan_string_free(out, len);
+            sanitized
+        }
+    }
+
+    #[test]
+    fn test_ffi() {
+        assert_eq!(sanitize(null(), "hello"), "hello");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(sanitize(null(), "hi 🏴!"), "hi !");
+
+        unsafe {
+            let ranges = [LangsanRange {
+                start: 0x20,
+                end: 0x7E,
+            }];
+            let policy = langsan_policy_new(ranges.as_ptr(), ranges.len());
+            assert!(!policy.is_null());
+            assert!(langsan_policy_forbid(policy, ['<' as u32].as_ptr(), 1));
+            assert!(langsan_is_allowed(policy, 'a' as u32));
+            assert!(!langsan_is_allowed(policy, '<' as u32));
+            assert!(!langsan_is_allowed(policy, 0xD800));
+            assert!(langsan_is_allowed(null(), '\n' as u32));
+            #[cfg(not(feature = "verbose"))]
+            assert_eq!(sanitize(policy, "a\nb<c"), "ac");
+
+            let mut offset = 0;
+            let input = "ab<c";
+            assert!(langsan_is_clean(
+                policy,
+                "abc".as_ptr().cast(),
+                3,
+                &mut offset
+            ));
+            assert!(!langsan_is_clean(
+                policy,
+                input.as_ptr().cast(),
+                input.len(),
+                &mut offset
+            ));
+            assert_eq!(offset, 2);
+            assert!(!langsan_is_clean(
+                null(),
+                b"ab\xFF".as_ptr().cast(),
+                3,
+                &mut offset
+            ));
+            assert_eq!(offset, 2);
+            // Allowed characters can still be removed in context, like a
+            // joiner outside an emoji sequence.
+            #[cfg(feature = "emoji-full")]
+            {
+                let input = "a\u{200D}b";
+                assert!(langsan_is_allowed(null(), 0x200D));
+                assert!(!langsan_is_clean(
+                    null(),
+                    input.as_ptr().cast(),
+                    input.len(),
+                    &mut offset
+                ));
+                assert_eq!(offset, 1);
+            }
+            langsan_policy_free(policy);
+
+            let invalid = [LangsanRange { start: 2, end: 1 }];
+            assert!(langsan_policy_new(invalid.as_ptr(), 1).is_null());
+            assert!(langsan_policy_new(null(), 1).is_null());
+            assert!(!langsan_policy_forbid(null_mut(), null(), 0));
+            assert!(langsan_sanitize(null(), b"\xFF".as_ptr().cast(), 1, &mut 0).is_null());
+            assert!(langsan_sanitize(null(), null(), 0, &mut 0).is_null());
+            assert!(langsan_sanitize(null(), "a".as_ptr().cast(), 1, null_mut()).is_null());
+
+            let policy = langsan_policy_default();
+            assert!(langsan_is_allowed(policy, 'a' as u32));
+            langsan_policy_free(policy);
+            langsan_policy_free(null_mut());
+            langsan_string_free(null_mut(), 0);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+/// Sanitization of large files, for cleaning datasets.
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use crate::{
+    corpus::{sanitize_bytes_lines, write_atomic},
+    CharPolicy, Stats,
+};
+
+/// Sanitize the file at `path` with `policy`, writing the result to
+/// `out_path`, which may be the same file. Returns what was removed.
+///
+/// The input is memory mapped and sanitized line by line, so memory use is
+/// bounded by the longest line, and a removed span never extends past the end
+/// of a line. Invalid UTF-8 is replaced with U+FFFD first. The output is
+/// written to a temporary file next to `out_path` and renamed over it, so an
+/// interruption can't leave it truncated.
+///
+/// ```no_run
+/// use langsan::{sanitize_file, Policy};
+///
+/// let stats = sanitize_file("corpus.jsonl", "corpus.clean.jsonl", &Policy::default())?;
+/// println!("removed {} bytes", stats.removed_bytes());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn sanitize_file<P>(
+    path: impl AsRef<Path>,
+    out_path: impl AsRef<Path>,
+    policy: &P,
+) -> io::Result<Stats>
+where
+    P: CharPolicy + ?Sized,
+{
+    // SAFETY: The map is only read while this function runs. If another
+    // process truncates the file meanwhile, reading it faults, as with any
+    // memory mapped file.
+    let map = unsafe { Mmap::map(&fs::File::open(path)?)? };
+
+    let mut stats = Stats::new();
+    // The map is moved in so it's closed before renaming, in case the output
+    // is the input.
+    write_atomic(out_path.as_ref(), |file| {
+        let map = map;
+        let mut output = BufWriter::new(file);
+        stats = sanitize_bytes_lines(&map, policy, &mut output)?;
+        output.flush()
+    })?;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+
+    #[test]
+    fn test_sanitize_file() {
+        let dir = std::env::temp_dir().join(format!("langsan-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("in.txt");
+        let out_path = dir.join("out.txt");
+        fs::write(&path, b"a\x01b\x01c\r\nclean\n\xFFd\n\x01").unwrap();
+
+        let policy = Policy::new(vec![0x20..=0x7E, 0x0A..=0x0A]).verbose(false);
+        let stats = sanitize_file(&path, &out_path, &policy).unwrap();
+        assert_eq!(fs::read(&out_path).unwrap(), b"ac\r\nclean\nd\n");
+        assert_eq!((stats.inputs(), stats.modified()), (4, 3));
+        assert_eq!(stats.removed_by_block()["Specials"], 1);
+
+        // In place, and empty files
+        sanitize_file(&path, &path, &policy).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"ac\r\nclean\nd\n");
+        fs::write(&path, b"").unwrap();
+        assert_eq!(sanitize_file(&path, &path, &policy).unwrap().inputs(), 0);
+        assert!(!dir.join("in.txt.langsan.tmp").exists());
+
+        assert!(sanitize_file(dir.join("missing"), &out_path, &policy).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+//! [`garde`] rules checking that strings are clean.
+//!
+//! ```
+//! use garde::Validate;
+//!
+//! #[derive(Validate)]
+//! struct Prompt {
+//!     #[garde(custom(langsan::garde::sanitized))]
+//!     text: String,
+//!     #[garde(inner(custom(langsan::garde::sanitized)))]
+//!     name: Option<String>,
+//! }
+//!
+//! assert!(Prompt { text: "Hi!".into(), name: None }.validate().is_ok());
+//! let report = Prompt { text: "Hi🏴!".into(), name: None }.validate().unwrap_err();
+//! assert_eq!(
+//!     report.to_string(),
+//!     "text: disallowed character U+1F3F4 at byte 2\n"
+//! );
+//! ```
+use crate::sanitize_strict;
+
+/// Check that sanitizing `value` with the active policy wouldn't modify it,
+/// for `#[garde(custom(langsan::garde::sanitized))]`. The error is that of
+/// [`sanitize_strict`] for the first character sanitization would change.
+/// Any validation context is accepted and ignored.
+pub fn sanitized<C: ?Sized>(value: &str, _context: &C) -> garde::Result {
+    sanitize_strict(value)
+        .map(|_| ())
+        .map_err(|e| garde::Error::new(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{with_policy, Policy};
+    use garde::Validate;
+
+    #[derive(Validate)]
+    struct Message {
+        #[garde(custom(sanitized))]
+        content: String,
+        #[garde(inner(custom(sanitized)))]
+        name: Option<String>,
+    }
+
+    struct Limits {
+        max: usize,
+    }
+
+    #[derive(Validate)]
+    #[garde(context(Limits as limits))]
+    struct Request {
+        #[garde(custom(sanitized), length(max = limits.max))]
+        prompt: String,
+    }
+
+    #[test]
+    fn test_sanitized() {
+        assert_eq!(sanitized("hello", &()), Ok(()));
+        assert_eq!(
+            sanitized("hi🏴", &()).unwrap_err().message(),
+            "disallowed character U+1F3F4 at byte 2"
+        );
+
+        let message = Message {
+            content: "hello".into(),
+            name: Some("bob🏴".into()),
+        };
+        let report = message.validate().unwrap_err();
+        let errors: Vec<_> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(errors, ["name"]);
+        let message = Message {
+            content: "hello".into(),
+            name: None,
+        };
+        assert!(message.validate().is_ok());
+
+        let limits = Limits { max: 8 };
+        let request = Request {
+            prompt: "hi🏴".into(),
+        };
+        assert!(request.validate_with(&limits).is_err());
+        let request = Request {
+            prompt: "hi".into(),
+        };
+        assert!(request.validate_with(&limits).is_ok());
+
+        // With the active policy
+        with_policy(Policy::new(&[0x61..=0x7A]), || {
+            assert!(sanitized("hello", &()).is_ok());
+            assert!(sanitized("Hello", &()).is_err());
+        });
+    }
+}
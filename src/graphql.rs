@@ -0,0 +1,71 @@
+/// [`async_graphql`] scalar support.
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+use crate::{CowStr, SanitizedString};
+
+/// A `SanitizedString` scalar, sanitized with the active policy during input
+/// coercion so resolvers never see unsanitized text. It is a string on the
+/// wire.
+#[Scalar(name = "SanitizedString")]
+impl ScalarType for SanitizedString {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Ok(CowStr::from(s)),
+            other => Err(InputValueError::expected_type(other)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptySubscription, Object, Request, Schema, Variables};
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, prompt: SanitizedString) -> SanitizedString {
+            prompt
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn send(&self, prompt: SanitizedString) -> usize {
+            prompt.len()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graphql() {
+        let schema = Schema::new(Query, Mutation, EmptySubscription);
+        assert!(schema.sdl().contains("scalar SanitizedString"));
+
+        let response = schema.execute(r#"{ echo(prompt: "hi🏴") }"#).await;
+        assert!(response.errors.is_empty());
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(response.data.to_string(), r#"{echo: "hi"}"#);
+
+        let request = Request::new("mutation($p: SanitizedString!) { send(prompt: $p) }")
+            .variables(Variables::from_json(serde_json::json!({"p": "hi🏴!"})));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty());
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(response.data.to_string(), "{send: 3}");
+
+        let response = schema.execute("{ echo(prompt: 1) }").await;
+        assert_eq!(response.errors.len(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+/// Sanitization of [`tonic`] gRPC messages.
+use tonic::{Request, Response, Status};
+
+use crate::Sanitize;
+
+impl<T: Sanitize> Sanitize for Request<T> {
+    fn sanitize(&mut self) -> usize {
+        self.get_mut().sanitize()
+    }
+
+    fn sanitize_strict(&self) -> Result<(), crate::Error> {
+        self.get_ref().sanitize_strict()
+    }
+}
+
+impl<T: Sanitize> Sanitize for Response<T> {
+    fn sanitize(&mut self) -> usize {
+        self.get_mut().sanitize()
+    }
+
+    fn sanitize_strict(&self) -> Result<(), crate::Error> {
+        self.get_ref().sanitize_strict()
+    }
+}
+
+/// Sanitize the message of an incoming `request` with the active policy.
+/// Tonic interceptors only see metadata, so call this (or [`strict_request`])
+/// at the top of a service method instead:
+///
+/// ```ignore
+/// async fn chat(&self, request: Request<ChatRequest>) -> Result<Response<ChatReply>, Status> {
+///     let request = langsan::sanitize_request(request);
+///     // ...
+/// }
+/// ```
+pub fn sanitize_request<T: Sanitize>(mut request: Request<T>) -> Request<T> {
+    request.sanitize();
+    request
+}
+
+/// Strict [`sanitize_request`]. The request is rejected with
+/// [`Status::invalid_argument`] if any string would be modified.
+pub fn strict_request<T: Sanitize>(request: Request<T>) -> Result<Request<T>, Status> {
+    match request.sanitize_strict() {
+        Ok(()) => Ok(request),
+        Err(e) => Err(Status::invalid_argument(e.to_string())),
+    }
+}
+
+/// Sanitize the message of an outgoing `response` with the active policy.
+pub fn sanitize_response<T: Sanitize>(mut response: Response<T>) -> Response<T> {
+    response.sanitize();
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct Prompt {
+        text: String,
+        id: u64,
+    }
+
+    crate::impl_sanitize!(Prompt { text });
+
+    #[test]
+    fn test_grpc() {
+        let prompt = |text: &str| Prompt {
+            text: text.into(),
+            id: 1,
+        };
+
+        let request = sanitize_request(Request::new(prompt("hi🏴")));
+        assert_eq!(request.get_ref().id, 1);
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(request.get_ref().text, "hi");
+
+        assert!(strict_request(Request::new(prompt("hi"))).is_ok());
+        let status = strict_request(Request::new(prompt("hi🏴"))).unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "disallowed character U+1F3F4 at byte 2");
+
+        let response = sanitize_response(Response::new(prompt("bye🏴")));
+        assert!(response.sanitize_strict().is_ok());
+    }
+}
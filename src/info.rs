@@ -0,0 +1,69 @@
+/// Information about how the crate was built.
+use crate::ranges::{self, EnabledRange};
+
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
+/// How langsan was built, from [`build_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct BuildInfo {
+    /// The version of langsan.
+    pub version: &'static str,
+    /// The cargo features enabled, sorted.
+    pub features: &'static [&'static str],
+    /// The ranges the default policy allows, as from [`ranges::enabled`].
+    pub ranges: &'static [EnabledRange],
+    /// A hash of the Unicode range and emoji data the policy was generated
+    /// from, which changes whenever the data does.
+    pub data_version: &'static str,
+    /// The version of Unicode the ranges are from, as for
+    /// [`ranges::UNICODE_VERSION`].
+    pub unicode_version: Option<&'static str>,
+}
+
+impl BuildInfo {
+    /// Returns true if the cargo `feature` was enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.binary_search(&feature).is_ok()
+    }
+}
+
+/// Which features and ranges were compiled in, and the version of the Unicode
+/// range data, so a service can check at startup that it was built with the
+/// policy it expects. Feature unification in a workspace can enable more
+/// features than a crate asks for.
+///
+/// ```
+/// let info = langsan::build_info();
+/// if info.has_feature("dangerous-allow-tags") {
+///     eprintln!("warning: langsan {} allows tag characters", info.version);
+/// }
+/// assert_eq!(info.ranges[1].name, "Basic Latin");
+/// ```
+pub const fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        features: FEATURES,
+        ranges: ranges::ENABLED,
+        data_version: DATA_VERSION,
+        unicode_version: ranges::UNICODE_VERSION,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info() {
+        let info = build_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.has_feature("verbose"), cfg!(feature = "verbose"));
+        assert_eq!(info.has_feature("serde"), cfg!(feature = "serde"));
+        assert!(!info.has_feature("nope"));
+        assert!(info.features.is_sorted());
+        assert_eq!(info.ranges.len(), crate::ENABLED_RANGES.len());
+        assert_eq!(info.data_version.len(), 16);
+    }
+}
@@ -0,0 +1,174 @@
+/// Sanitization of [`serde_json::Value`]s.
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    batch::{sanitize_keys, StringKeys},
+    policy::with_current,
+    san::sanitize_in_place,
+    sanitize_strict, Error,
+};
+
+/// Recursively sanitize every string in `value` with the active policy,
+/// including object keys if `keys` is true. Like [`sanitize_hash_map`], an
+/// entry whose sanitized key is already present is dropped, and of several
+/// modified keys sanitizing to the same key, the least is kept. Returns the
+/// number of strings modified.
+///
+/// [`sanitize_hash_map`]: crate::sanitize_hash_map
+pub fn sanitize_json(value: &mut Value, keys: bool) -> usize {
+    with_current(|policy| {
+        let mut scratch = String::new();
+        let mut modified = 0;
+        let mut stack = vec![value];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::String(s) => {
+                    modified += sanitize_in_place(s, policy, &mut scratch) as usize;
+                }
+                Value::Array(values) => stack.extend(values.iter_mut()),
+                Value::Object(map) => {
+                    if keys {
+                        modified += sanitize_keys(map, policy, &mut scratch);
+                    }
+                    stack.extend(map.values_mut());
+                }
+                Value::Null | Value::Bool(_) | Value::Number(_) => {}
+            }
+        }
+        modified
+    })
+}
+
+impl StringKeys for Map<String, Value> {
+    type Value = Value;
+
+    fn string_keys(&self) -> impl Iterator<Item = &String> {
+        self.keys()
+    }
+
+    fn remove_key(&mut self, key: &str) -> Option<Value> {
+        self.remove(key)
+    }
+
+    fn insert_vacant(&mut self, key: String, value: Value) {
+        self.entry(key).or_insert(value);
+    }
+}
+
+/// Strict [`sanitize_json`]. Returns an [`Error::Disallowed`] for the first
+/// string (or object key if `keys` is true) that sanitization would modify,
+/// with the offset relative to that string.
+pub fn sanitize_json_strict(value: &Value, keys: bool) -> Result<(), Error> {
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::String(s) => {
+                sanitize_strict(s)?;
+            }
+            Value::Array(values) => stack.extend(values.iter()),
+            Value::Object(map) => {
+                for (key, value) in map {
+                    if keys {
+                        sanitize_strict(key)?;
+                    }
+                    stack.push(value);
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Deeply sanitize any serde-able `value` by transcoding it through a
+/// [`Value`] and [`sanitize_json`], including map keys. This works for
+/// arbitrary nested types without a derive, at the cost of a round-trip, and
+/// is subject to the limitations of [`Value`] (e.g. no 128 bit integers).
+pub fn sanitize_value<T>(value: T) -> Result<T, Error>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut json = serde_json::to_value(value).map_err(|e| Error::Serde(e.to_string()))?;
+    sanitize_json(&mut json, true);
+    serde_json::from_value(json).map_err(|e| Error::Serde(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_sanitize_json() {
+        let mut value = json!({
+            "role": "user",
+            "content": ["Hello🏴", {"nested": "world🏴!"}],
+            "key🏴": 42,
+            "role🏴": "ignored",
+        });
+        assert_eq!(sanitize_json(&mut value.clone(), false), 2);
+
+        assert_eq!(sanitize_json(&mut value, true), 4);
+        assert_eq!(
+            value,
+            json!({
+                "role": "user",
+                "content": ["Hello", {"nested": "world!"}],
+                "key": 42,
+            })
+        );
+        assert_eq!(sanitize_json(&mut value, true), 0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_sanitize_value() {
+        use std::collections::HashMap;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Call {
+            name: String,
+            args: HashMap<String, Vec<String>>,
+            id: u64,
+        }
+
+        let call = Call {
+            name: "search🏴".into(),
+            args: [("query🏴".into(), vec!["cats🏴".into()])].into(),
+            id: 7,
+        };
+        assert_eq!(
+            sanitize_value(call).unwrap(),
+            Call {
+                name: "search".into(),
+                args: [("query".into(), vec!["cats".into()])].into(),
+                id: 7,
+            }
+        );
+        assert_eq!(
+            sanitize_value(("a🏴".to_string(), 1)).unwrap(),
+            ("a".to_string(), 1)
+        );
+        assert!(matches!(sanitize_value(u128::MAX), Err(Error::Serde(_))));
+    }
+
+    #[test]
+    fn test_sanitize_json_strict() {
+        let value = json!({"role": "user", "content": ["Hello", {"n": 1}]});
+        assert_eq!(sanitize_json_strict(&value, true), Ok(()));
+
+        let value = json!({"content": ["Hello", {"nested": "hi🏴"}]});
+        assert_eq!(
+            sanitize_json_strict(&value, false),
+            Err(Error::Disallowed {
+                offset: 2, c: '🏴'
+            })
+        );
+
+        let value = json!({"key🏴": 42});
+        assert_eq!(sanitize_json_strict(&value, false), Ok(()));
+        assert!(sanitize_json_strict(&value, true).is_err());
+    }
+}
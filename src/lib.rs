@@ -1,8 +1,156 @@
+pub(crate) mod error;
+pub use error::Error;
+
 pub(crate) mod cow;
-pub use cow::CowStr;
+pub use cow::{verify, verify_with, CowStr, SanitizedString};
+
+pub(crate) mod emoji;
+pub use emoji::{classify_emoji, EmojiKind};
+
+pub(crate) mod literal;
+pub use literal::is_clean_const;
 
 pub(crate) mod san;
-pub use san::sanitize;
+pub use san::{
+    sanitize, sanitize_both, sanitize_both_with, sanitize_bytes, sanitize_bytes_lossy,
+    sanitize_outcome, sanitize_outcome_with, sanitize_spans, sanitize_strict, sanitize_strict_max,
+    sanitize_with, Outcome,
+};
+
+pub(crate) mod chars;
+pub use chars::SanitizeChars;
+
+pub(crate) mod deep;
+pub use deep::Sanitize;
+#[doc(hidden)]
+pub use deep::{__sanitize_named, __sanitize_strict_named, __try_sanitize_named};
+#[cfg(feature = "derive")]
+pub use langsan_derive::Sanitize;
+
+pub(crate) mod sanitized;
+pub use sanitized::Sanitized;
+
+pub(crate) mod view;
+pub use view::SanitizedView;
+
+pub mod chat;
+
+pub(crate) mod fence;
+pub use fence::{escape_delimiters, fence};
+
+pub(crate) mod canary;
+pub use canary::{Canary, Leak};
+
+#[cfg(feature = "aho-corasick")]
+pub(crate) mod denylist;
+#[cfg(feature = "aho-corasick")]
+pub use denylist::{Denylist, DenylistMatch};
+
+pub(crate) mod analyze;
+pub use analyze::{analyze, analyze_with, risk_score, Analysis, Detection, Finding, Severity};
+
+pub(crate) mod threat;
+pub use threat::{threat_score, Factor, Threat, Verdict};
+
+pub(crate) mod pipeline;
+pub use pipeline::{Normalize, Pipeline, PipelineStream, Processed};
+
+pub(crate) mod batch;
+pub use batch::{sanitize_all, sanitize_all_in_place, sanitize_btree_map, sanitize_hash_map};
+
+#[cfg(feature = "serde")]
+pub(crate) mod json;
+#[cfg(feature = "serde")]
+pub use json::{sanitize_json, sanitize_json_strict, sanitize_value};
+
+#[cfg(feature = "serde")]
+pub mod serde_str;
+
+#[cfg(feature = "rkyv")]
+pub(crate) mod archive;
+#[cfg(feature = "rkyv")]
+pub use archive::ArchivedCowStr;
+
+#[cfg(any(feature = "sqlx", feature = "diesel", feature = "rusqlite"))]
+pub(crate) mod sql;
+
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub(crate) mod web;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub use web::{SanitizedJson, StrictJson};
+
+#[cfg(feature = "tower")]
+pub(crate) mod middleware;
+#[cfg(feature = "tower")]
+pub use middleware::{SanitizeLayer, SanitizeService, SanitizedBody};
+
+#[cfg(feature = "tonic")]
+pub(crate) mod grpc;
+#[cfg(feature = "tonic")]
+pub use grpc::{sanitize_request, sanitize_response, strict_request};
+
+#[cfg(feature = "clap")]
+pub(crate) mod args;
+#[cfg(feature = "clap")]
+pub use args::SanitizedParser;
+
+#[cfg(feature = "async-graphql")]
+pub(crate) mod graphql;
+
+#[cfg(feature = "sse")]
+pub(crate) mod sse;
+#[cfg(feature = "sse")]
+pub use sse::{sanitize_sse, SseSanitizer, SseStream};
+
+#[cfg(feature = "wasm")]
+pub(crate) mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmPolicy;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub(crate) mod report;
+pub use report::Report;
+
+#[cfg(feature = "color")]
+pub(crate) mod color;
+
+pub(crate) mod stats;
+pub use stats::Stats;
+
+pub(crate) mod corpus;
+pub use corpus::Corpus;
+
+#[cfg(feature = "mmap")]
+pub(crate) mod file;
+#[cfg(feature = "mmap")]
+pub use file::sanitize_file;
+
+#[cfg(feature = "test-util")]
+pub mod testing;
+
+#[cfg(feature = "validator")]
+pub(crate) mod validate;
+#[cfg(feature = "validator")]
+pub use validate::validate_sanitized;
+
+#[cfg(feature = "garde")]
+pub mod garde;
+
+#[cfg(feature = "transliterate")]
+pub(crate) mod translit;
+#[cfg(feature = "transliterate")]
+pub use translit::{transliterate, transliterate_with};
+
+pub(crate) mod policy;
+pub use policy::{
+    is_char_allowed, is_char_allowed_with, register_policy, set_default_policy, with_named_policy,
+    with_policy, CharPolicy, Decision, EmojiPolicy, Marker, Policy,
+};
 
 pub mod ranges;
-pub use ranges::ENABLED_RANGES;
+pub use ranges::{languages::Lang, ENABLED_RANGES};
+
+pub(crate) mod info;
+pub use info::{build_info, BuildInfo};
@@ -0,0 +1,146 @@
+/// Compile-time checked string literals.
+use crate::{emoji, policy::is_guarded, san::FORBIDDEN_EMOJI, ENABLED_RANGES};
+
+/// A borrowed [`CowStr`] of a string literal, checked at compile time with
+/// [`is_clean_const`], so nothing is scanned at runtime. The build fails
+/// if the literal contains characters the [`Default`] policy would remove.
+///
+/// ```
+/// use langsan::{cowstr, CowStr};
+///
+/// const GREETING: CowStr<'static> = cowstr!("Hello, world!");
+/// assert!(GREETING.is_borrowed());
+/// assert_eq!(cowstr!("Hi!").as_ref(), "Hi!");
+/// ```
+///
+/// ```compile_fail
+/// let flag = langsan::cowstr!("🏴");
+/// ```
+///
+/// [`CowStr`]: crate::CowStr
+/// [`is_clean_const`]: crate::is_clean_const
+/// [`Default`]: crate::Policy#impl-Default-for-Policy
+#[macro_export]
+macro_rules! cowstr {
+    ($s:literal) => {{
+        const _: () = ::core::assert!(
+            $crate::is_clean_const($s),
+            "string literal contains characters sanitization would remove"
+        );
+        $crate::CowStr::__from_clean_literal($s)
+    }};
+}
+
+/// Returns true if sanitizing `s` with the [`Default`] policy would not modify
+/// it. This only checks the [`ENABLED_RANGES`], so it can be used in `const`
+/// assertions on static prompts and templates:
+///
+/// ```
+/// use langsan::is_clean_const;
+///
+/// const PROMPT: &str = "You are a helpful assistant.";
+/// const _: () = assert!(is_clean_const(PROMPT));
+/// assert!(!is_clean_const("Hi 🏴"));
+/// ```
+///
+/// At runtime, a stricter active policy may still remove characters.
+///
+/// [`Default`]: crate::Policy#impl-Default-for-Policy
+/// [`ENABLED_RANGES`]: crate::ENABLED_RANGES
+pub const fn is_clean_const(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let (mut prev, mut prev2) = (None, None);
+    let mut i = 0;
+    while i < bytes.len() {
+        let (c, len) = decode(bytes, i);
+        let next = if i + len < bytes.len() {
+            Some(decode(bytes, i + len).0)
+        } else {
+            None
+        };
+        if !is_allowed(c)
+            || cfg!(feature = "emoji-full")
+                && emoji::is_joiner(c)
+                && !emoji::in_literal_sequence(c, prev, prev2, next)
+        {
+            return false;
+        }
+        prev2 = prev;
+        prev = Some(c);
+        i += len;
+    }
+    true
+}
+
+/// Returns true if the [`Default`] policy allows `c`.
+///
+/// [`Default`]: crate::Policy#impl-Default-for-Policy
+const fn is_allowed(c: char) -> bool {
+    if is_guarded(c) {
+        return false;
+    }
+    let mut i = 0;
+    while i < FORBIDDEN_EMOJI.len() {
+        if FORBIDDEN_EMOJI[i] == c {
+            return false;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < ENABLED_RANGES.len() {
+        let range = &ENABLED_RANGES[i];
+        if *range.start() <= c as u32 && c as u32 <= *range.end() {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Decode the character starting at byte `i` of valid UTF-8 `bytes`, and its
+/// length.
+const fn decode(bytes: &[u8], i: usize) -> (char, usize) {
+    let (len, mut code) = match bytes[i] {
+        b @ 0x00..=0x7F => (1, b as u32),
+        b @ 0xC0..=0xDF => (2, (b & 0x1F) as u32),
+        b @ 0xE0..=0xEF => (3, (b & 0x0F) as u32),
+        b => (4, (b & 0x07) as u32),
+    };
+    let mut j = 1;
+    while j < len {
+        code = code << 6 | (bytes[i + j] & 0x3F) as u32;
+        j += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => (c, len),
+        None => panic!("invalid UTF-8"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sanitize, CowStr};
+
+    #[test]
+    fn test_is_clean_const() {
+        for s in [
+            "",
+            "hello \t\n",
+            "a🏴b",
+            "\u{E0041}",
+            "x\u{7F}",
+            "€ £ ß Ж 中",
+        ] {
+            assert_eq!(is_clean_const(s), sanitize(s).is_none(), "{s:?}");
+        }
+        #[cfg(feature = "emoji-full")]
+        for s in ["👨‍👩‍👧", "🏳️‍🌈", "1️⃣", "a\u{200D}b", "\u{FE0F}", "👨\u{200D}"]
+        {
+            assert_eq!(is_clean_const(s), sanitize(s).is_none(), "{s:?}");
+        }
+
+        const HI: CowStr<'static> = crate::cowstr!("hi");
+        assert_eq!(HI, CowStr::from("hi"));
+    }
+}
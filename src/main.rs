@@ -0,0 +1,445 @@
+//! The `langsan` command-line tool. Sanitizes files or stdin line by line, so
+//! a removed span never extends past the end of a line.
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, Write},
+    ops::{Range, RangeInclusive},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, ValueEnum};
+use langsan::{
+    ranges::block_name, risk_score, sanitize_outcome_with, sanitize_with, Policy, Severity,
+};
+use serde::Serialize;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Sanitize language model input and output.")]
+struct Args {
+    /// Files to sanitize. Reads stdin if none are given or for `-`.
+    files: Vec<PathBuf>,
+
+    /// Only allow this inclusive range of code points, in hex, instead of the
+    /// ranges enabled at build time. May be repeated.
+    #[arg(long, value_name = "START-END", value_parser = parse_range)]
+    allow: Vec<RangeInclusive<u32>>,
+
+    /// Also forbid these characters.
+    #[arg(long, value_name = "CHARS")]
+    forbid: Option<String>,
+
+    /// Don't modify anything. Report the first disallowed character of each
+    /// input and exit with 1 if there were any.
+    #[arg(long, conflicts_with = "in_place")]
+    strict: bool,
+
+    /// Don't modify anything. Describe everything sanitization would remove
+    /// from each input, one line per input, and exit with 1 if there was
+    /// anything.
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["in_place", "strict"])]
+    report: Option<ReportFormat>,
+
+    /// Don't modify anything. Print each input with what sanitization would
+    /// remove highlighted in red, and exit with 1 if there was anything.
+    #[arg(long, conflicts_with_all = ["in_place", "strict", "report"])]
+    highlight: bool,
+
+    /// Sanitize files in place rather than writing them to stdout.
+    #[arg(short, long)]
+    in_place: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    /// A JSON object per input.
+    Json,
+}
+
+impl Args {
+    fn policy(&self) -> Policy {
+        let policy = match self.allow.is_empty() {
+            true => Policy::default(),
+            false => Policy::new(self.allow.clone()),
+        };
+        match &self.forbid {
+            Some(chars) => policy.forbid(chars.chars()),
+            None => policy,
+        }
+    }
+}
+
+/// Parse a hex code point range like `20-7E` or a single code point like `A`.
+fn parse_range(s: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |s: &str| {
+        let s = s.trim();
+        let s = s.strip_prefix("0x").or(s.strip_prefix("U+")).unwrap_or(s);
+        u32::from_str_radix(s, 16).map_err(|e| format!("invalid code point '{}': {}", s, e))
+    };
+    let (start, end) = match s.split_once('-') {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => (parse(s)?, parse(s)?),
+    };
+    if start > end || end > char::MAX as u32 {
+        return Err(format!("invalid range {:X}-{:X}", start, end));
+    }
+    Ok(start..=end)
+}
+
+/// Split the line ending off a `line`.
+fn split_ending(line: &[u8]) -> (&[u8], &[u8]) {
+    let content = line.strip_suffix(b"\n").unwrap_or(line);
+    let content = content.strip_suffix(b"\r").unwrap_or(content);
+    line.split_at(content.len())
+}
+
+/// Sanitize `input` line by line onto `output`, converting invalid UTF-8
+/// lossily first. Returns the number of lines modified.
+fn sanitize_lines(
+    policy: &Policy,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<usize> {
+    let mut modified = 0;
+    let mut buf = Vec::new();
+    while input.read_until(b'\n', &mut buf)? > 0 {
+        let (line, ending) = split_ending(&buf);
+        let line = String::from_utf8_lossy(line);
+        match (sanitize_with(&line, policy), &line) {
+            (Some(sanitized), _) => {
+                output.write_all(sanitized.as_bytes())?;
+                modified += 1;
+            }
+            (None, Cow::Owned(lossy)) => {
+                output.write_all(lossy.as_bytes())?;
+                modified += 1;
+            }
+            (None, Cow::Borrowed(line)) => output.write_all(line.as_bytes())?,
+        }
+        output.write_all(ending)?;
+        buf.clear();
+    }
+    output.flush()?;
+    Ok(modified)
+}
+
+/// Write `input` onto `output` line by line, highlighting what sanitization
+/// would remove. Returns the number of lines with anything removed.
+fn highlight_lines(
+    policy: &Policy,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<usize> {
+    let mut highlighted = 0;
+    let mut buf = Vec::new();
+    while input.read_until(b'\n', &mut buf)? > 0 {
+        let (line, ending) = split_ending(&buf);
+        let line = String::from_utf8_lossy(line);
+        match sanitize_outcome_with(&line, policy).report() {
+            Some(report) if report.removed().is_some() => {
+                output.write_all(report.highlight(&line).as_bytes())?;
+                highlighted += 1;
+            }
+            _ => output.write_all(line.as_bytes())?,
+        }
+        output.write_all(ending)?;
+        buf.clear();
+    }
+    output.flush()?;
+    Ok(highlighted)
+}
+
+/// Where the first character of `input` that sanitization would modify is, as
+/// a 1-based line and column in characters, and what it is. Invalid UTF-8 is
+/// reported as U+FFFD.
+fn find_disallowed(
+    policy: &Policy,
+    mut input: impl BufRead,
+) -> io::Result<Option<(usize, usize, char)>> {
+    let mut buf = Vec::new();
+    let mut line_number = 0;
+    while input.read_until(b'\n', &mut buf)? > 0 {
+        line_number += 1;
+        let (line, _) = split_ending(&buf);
+        let line = String::from_utf8_lossy(line);
+        let lossy = matches!(line, Cow::Owned(_));
+        let mut chars = line.chars().enumerate();
+        if let Some((column, c)) = chars
+            .find(|&(_, c)| !policy.is_allowed(c) || (lossy && c == char::REPLACEMENT_CHARACTER))
+        {
+            return Ok(Some((line_number, column + 1, c)));
+        }
+        buf.clear();
+    }
+    Ok(None)
+}
+
+/// What sanitization would remove from an input.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct FileReport {
+    path: PathBuf,
+    /// The total number of bytes that would be removed.
+    removed_bytes: usize,
+    /// The total number of characters that would be replaced.
+    replaced: usize,
+    /// The combined risk of the findings, from 0 to 1.
+    risk_score: f64,
+    findings: Vec<Finding>,
+}
+
+/// What sanitization would remove from a line.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+struct Finding {
+    /// 1-based line number.
+    line: usize,
+    /// Byte range of the line that would be removed, if any.
+    removed: Option<Range<usize>>,
+    removed_bytes: usize,
+    replaced: usize,
+    severity: Severity,
+    /// The number of offending characters, by Unicode block.
+    blocks: BTreeMap<&'static str, usize>,
+    /// The line isn't valid UTF-8.
+    invalid_utf8: bool,
+    /// The ASCII text smuggled in Unicode tag characters, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<String>,
+}
+
+/// Decode the ASCII text hidden in any Unicode tag characters in `line`.
+fn decode_tags(line: &str) -> Option<String> {
+    let tags: String = line
+        .chars()
+        .filter_map(|c| match c as u32 {
+            c @ 0xE0020..=0xE007E => char::from_u32(c - 0xE0000),
+            _ => None,
+        })
+        .collect();
+    (!tags.is_empty()).then_some(tags)
+}
+
+/// Describe everything sanitization would remove from `input`, line by line.
+fn report_lines(policy: &Policy, path: &Path, mut input: impl BufRead) -> io::Result<FileReport> {
+    let mut report = FileReport {
+        path: path.to_owned(),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    let mut line_number = 0;
+    while input.read_until(b'\n', &mut buf)? > 0 {
+        line_number += 1;
+        let (line, _) = split_ending(&buf);
+        let line = String::from_utf8_lossy(line);
+        let invalid_utf8 = matches!(line, Cow::Owned(_));
+        let outcome = sanitize_outcome_with(&line, policy);
+        if outcome.is_clean() && !invalid_utf8 {
+            buf.clear();
+            continue;
+        }
+        let mut finding = Finding {
+            line: line_number,
+            invalid_utf8,
+            tags: decode_tags(&line),
+            ..Default::default()
+        };
+        if let Some(r) = outcome.report() {
+            finding.removed = r.removed();
+            finding.removed_bytes = r.removed_bytes();
+            finding.replaced = r.replaced();
+            finding.severity = r.severity();
+        }
+        if invalid_utf8 {
+            finding.severity = finding.severity.max(Severity::Low);
+        }
+        for c in line.chars().filter(|&c| !policy.is_allowed(c)) {
+            *finding.blocks.entry(block_name(c)).or_default() += 1;
+        }
+        report.removed_bytes += finding.removed_bytes;
+        report.replaced += finding.replaced;
+        report.findings.push(finding);
+        buf.clear();
+    }
+    report.risk_score = risk_score(report.findings.iter().map(|f| f.severity));
+    Ok(report)
+}
+
+/// Sanitize the file at `path` in place, only writing it if it changed.
+fn sanitize_file(policy: &Policy, path: &Path) -> io::Result<()> {
+    let input = fs::read(path)?;
+    let mut output = Vec::with_capacity(input.len());
+    if sanitize_lines(policy, input.as_slice(), &mut output)? == 0 {
+        return Ok(());
+    }
+    // Write a sibling and rename it over the original so an interruption
+    // can't leave a truncated file.
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".langsan.tmp");
+    fs::write(&tmp, output)?;
+    fs::rename(&tmp, path)
+}
+
+fn run(args: &Args, policy: &Policy, path: &Path) -> io::Result<bool> {
+    let stdin = path == Path::new("-");
+    if args.strict {
+        let found = match stdin {
+            true => find_disallowed(policy, io::stdin().lock())?,
+            false => find_disallowed(policy, io::BufReader::new(fs::File::open(path)?))?,
+        };
+        if let Some((line, column, c)) = found {
+            println!(
+                "{}:{}:{}: disallowed character U+{:04X}",
+                path.display(),
+                line,
+                column,
+                c as u32
+            );
+        }
+        return Ok(found.is_none());
+    }
+    if let Some(ReportFormat::Json) = args.report {
+        let report = match stdin {
+            true => report_lines(policy, path, io::stdin().lock())?,
+            false => report_lines(policy, path, io::BufReader::new(fs::File::open(path)?))?,
+        };
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(report.findings.is_empty());
+    }
+    if args.highlight {
+        let stdout = io::BufWriter::new(io::stdout().lock());
+        let highlighted = match stdin {
+            true => highlight_lines(policy, io::stdin().lock(), stdout)?,
+            false => highlight_lines(policy, io::BufReader::new(fs::File::open(path)?), stdout)?,
+        };
+        return Ok(highlighted == 0);
+    }
+    if args.in_place && !stdin {
+        sanitize_file(policy, path)?;
+        return Ok(true);
+    }
+    let stdout = io::BufWriter::new(io::stdout().lock());
+    match stdin {
+        true => sanitize_lines(policy, io::stdin().lock(), stdout)?,
+        false => sanitize_lines(policy, io::BufReader::new(fs::File::open(path)?), stdout)?,
+    };
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let mut args = Args::parse();
+    let policy = args.policy();
+    if args.files.is_empty() {
+        args.files.push("-".into());
+    }
+
+    let mut clean = true;
+    for path in &args.files {
+        match run(&args, &policy, path) {
+            Ok(ok) => clean &= ok,
+            Err(e) => {
+                eprintln!("langsan: {}: {}", path.display(), e);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    match clean {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("20-7E"), Ok(0x20..=0x7E));
+        assert_eq!(parse_range("0x20 - U+7e"), Ok(0x20..=0x7E));
+        assert_eq!(parse_range("41"), Ok(0x41..=0x41));
+        assert!(parse_range("7E-20").is_err());
+        assert!(parse_range("110000").is_err());
+        assert!(parse_range("nope").is_err());
+    }
+
+    #[test]
+    fn test_sanitize_lines() {
+        let policy = Policy::new(vec![0x20..=0x7E, 0x0A..=0x0A]);
+        let mut output = Vec::new();
+        let input = b"a\x01b\x01c\r\nclean\n\x01\nd\xFF\xFF";
+        assert_eq!(sanitize_lines(&policy, &input[..], &mut output).unwrap(), 3);
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(output, b"ac\r\nclean\n\nd");
+
+        let policy = policy.forbid(['c']);
+        assert_eq!(
+            find_disallowed(&policy, "ab\nxyz\nabc".as_bytes()).unwrap(),
+            Some((3, 3, 'c'))
+        );
+        assert_eq!(find_disallowed(&policy, "ab\n".as_bytes()).unwrap(), None);
+        assert_eq!(
+            find_disallowed(&policy, b"ab\xFF".as_slice()).unwrap(),
+            Some((1, 3, char::REPLACEMENT_CHARACTER))
+        );
+    }
+
+    #[test]
+    fn test_highlight_lines() {
+        let policy = Policy::new(vec![0x20..=0x7E, 0x0A..=0x0A]);
+        let mut output = Vec::new();
+        let input = "a\u{E0041}b\r\nclean\n";
+        assert_eq!(
+            highlight_lines(&policy, input.as_bytes(), &mut output).unwrap(),
+            1
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "a\x1b[7;31m<U+E0041>\x1b[0mb\r\nclean\n"
+        );
+    }
+
+    #[test]
+    fn test_report_lines() {
+        let policy = Policy::default();
+        let mut input = "clean\nhi \u{E0041}\u{E0042}\u{E007F} there\n"
+            .as_bytes()
+            .to_vec();
+        input.extend_from_slice(b"ok\xFF\n");
+        let report = report_lines(&policy, Path::new("-"), input.as_slice()).unwrap();
+        assert_eq!(report.findings.len(), 2);
+
+        let finding = &report.findings[0];
+        assert_eq!(finding.line, 2);
+        assert_eq!(finding.removed, Some(3..15));
+        assert_eq!(finding.removed_bytes, 12);
+        assert_eq!(finding.blocks, BTreeMap::from([("Tags", 3)]));
+        assert_eq!(finding.tags.as_deref(), Some("AB"));
+        assert_eq!(finding.severity, Severity::High);
+        assert!(!finding.invalid_utf8);
+
+        let finding = &report.findings[1];
+        assert_eq!(finding.line, 3);
+        assert!(finding.invalid_utf8);
+        assert_eq!(finding.tags, None);
+        assert_eq!(report.removed_bytes, 12 + finding.removed_bytes);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["findings"][0]["blocks"]["Tags"], 3);
+        assert_eq!(json["findings"][0]["removed"]["start"], 3);
+        assert_eq!(json["findings"][0]["severity"], "high");
+        assert!(report.risk_score > 0.8);
+    }
+
+    #[test]
+    fn test_sanitize_file() {
+        let path = std::env::temp_dir().join(format!("langsan-test-{}.txt", std::process::id()));
+        fs::write(&path, "hi 🏴 there\nclean\n").unwrap();
+        sanitize_file(&Policy::default(), &path).unwrap();
+        let sanitized = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(sanitized, "hi  there\nclean\n");
+        assert!(sanitized.ends_with(" there\nclean\n"));
+    }
+}
@@ -0,0 +1,476 @@
+/// Tower middleware sanitizing HTTP request and response bodies.
+use std::{
+    borrow::Cow,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    HeaderMap, Request, Response, StatusCode,
+};
+use http_body::Body;
+use http_body_util::{BodyExt, Either, Full, LengthLimitError, Limited};
+use tower::{Layer, Service};
+
+use crate::{sanitize_json, sanitize_with, with_policy, Policy};
+
+/// The body type produced by [`SanitizeService`]: the original body if its
+/// content type isn't sanitized, otherwise the buffered, sanitized one.
+pub type SanitizedBody<B> = Either<B, Full<Bytes>>;
+
+/// A [`Layer`] that buffers and sanitizes request and response bodies whose
+/// `Content-Type` is in an allowlist, `text/plain` and `application/json` by
+/// default. Other bodies, including streams like `text/event-stream`, pass
+/// through untouched.
+///
+/// Compressed bodies, with a `Content-Encoding` other than `identity`, can't
+/// be sanitized as they are. Such requests are rejected with `415 Unsupported
+/// Media Type`, so encoding a body can't bypass sanitization, while such
+/// responses pass through, since they come from the wrapped service.
+///
+/// Bodies are buffered up to a [maximum size], 2 MiB by default. A larger
+/// request is rejected with `413 Payload Too Large`, and a larger response is
+/// replaced with `502 Bad Gateway`, rather than passed on unsanitized.
+///
+/// JSON bodies are sanitized string by string, including object keys, so
+/// they remain valid JSON. Anything else is sanitized as text, with invalid
+/// UTF-8 converted lossily first.
+///
+/// [maximum size]: SanitizeLayer::max_body_size
+#[derive(Debug, Clone)]
+pub struct SanitizeLayer {
+    config: Arc<Config>,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    policy: Policy,
+    content_types: Vec<Cow<'static, str>>,
+    requests: bool,
+    responses: bool,
+    max_body_size: usize,
+}
+
+/// The default [`SanitizeLayer::max_body_size`].
+const DEFAULT_MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// An error reading a body.
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Why a body couldn't be buffered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BufferError {
+    /// It's larger than the `max_body_size`.
+    TooLarge,
+    /// It couldn't be read.
+    Read,
+}
+
+/// How a body is sanitized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Text,
+    Json,
+}
+
+impl SanitizeLayer {
+    /// Sanitize request and response bodies with `policy`.
+    pub fn new(policy: Policy) -> Self {
+        Self {
+            config: Arc::new(Config {
+                policy,
+                content_types: vec!["text/plain".into(), "application/json".into()],
+                requests: true,
+                responses: true,
+                max_body_size: DEFAULT_MAX_BODY_SIZE,
+            }),
+        }
+    }
+
+    /// Replace the content type allowlist. Types are compared to the media
+    /// type of the `Content-Type` header, ignoring case and any parameters.
+    /// Types ending in `json` are sanitized as JSON.
+    pub fn content_types<I, T>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Cow<'static, str>>,
+    {
+        Arc::make_mut(&mut self.config).content_types = types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether to sanitize request bodies. Defaults to true.
+    pub fn requests(mut self, enabled: bool) -> Self {
+        Arc::make_mut(&mut self.config).requests = enabled;
+        self
+    }
+
+    /// Whether to sanitize response bodies. Defaults to true.
+    pub fn responses(mut self, enabled: bool) -> Self {
+        Arc::make_mut(&mut self.config).responses = enabled;
+        self
+    }
+
+    /// The most bytes of a body to buffer for sanitization. Defaults to 2 MiB.
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        Arc::make_mut(&mut self.config).max_body_size = bytes;
+        self
+    }
+}
+
+impl Default for SanitizeLayer {
+    fn default() -> Self {
+        Self::new(Policy::default())
+    }
+}
+
+impl<S> Layer<S> for SanitizeLayer {
+    type Service = SanitizeService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SanitizeService {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl Config {
+    /// How a body with `headers` should be sanitized, if at all.
+    fn kind(&self, headers: &HeaderMap) -> Option<Kind> {
+        let content_type = headers.get(CONTENT_TYPE)?.to_str().ok()?;
+        let essence = content_type.split(';').next()?.trim();
+        if !self
+            .content_types
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(essence))
+        {
+            return None;
+        }
+        match essence.to_ascii_lowercase().ends_with("json") {
+            true => Some(Kind::Json),
+            false => Some(Kind::Text),
+        }
+    }
+
+    /// Sanitize `bytes` as `kind`.
+    fn sanitize(&self, kind: Kind, bytes: Bytes) -> Bytes {
+        if kind == Kind::Json {
+            if let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+                let modified = with_policy(self.policy.clone(), || sanitize_json(&mut value, true));
+                return match modified {
+                    0 => bytes,
+                    // Serializing a `Value` can't fail
+                    _ => serde_json::to_vec(&value)
+                        .map(Bytes::from)
+                        .unwrap_or_default(),
+                };
+            }
+            // Not valid JSON, so the best we can do is sanitize it as text
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        match (sanitize_with(&text, &self.policy), text) {
+            (Some(sanitized), _) => sanitized.into(),
+            (None, Cow::Owned(lossy)) => lossy.into(),
+            (None, Cow::Borrowed(_)) => bytes,
+        }
+    }
+
+    /// Buffer and sanitize `body` as `kind`, updating the `Content-Length` in
+    /// `headers`.
+    async fn buffer<B>(
+        &self,
+        kind: Kind,
+        headers: &mut HeaderMap,
+        body: B,
+    ) -> Result<Full<Bytes>, BufferError>
+    where
+        B: Body<Data = Bytes>,
+        B::Error: Into<BoxError>,
+    {
+        // Don't read a body that says it's too large
+        let len = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok()?.parse::<usize>().ok());
+        if len.is_some_and(|len| len > self.max_body_size) {
+            return Err(BufferError::TooLarge);
+        }
+        let bytes = match Limited::new(body, self.max_body_size).collect().await {
+            Ok(collected) => self.sanitize(kind, collected.to_bytes()),
+            Err(e) if e.is::<LengthLimitError>() => return Err(BufferError::TooLarge),
+            Err(_) => return Err(BufferError::Read),
+        };
+        headers.insert(CONTENT_LENGTH, bytes.len().into());
+        Ok(Full::new(bytes))
+    }
+}
+
+/// Whether a body with `headers` is compressed, or otherwise encoded, so it
+/// would be garbled by sanitizing it as it is.
+fn is_encoded(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .any(|encoding| !encoding.as_bytes().eq_ignore_ascii_case(b"identity"))
+}
+
+/// The [`Service`] produced by [`SanitizeLayer`].
+#[derive(Debug, Clone)]
+pub struct SanitizeService<S> {
+    inner: S,
+    config: Arc<Config>,
+}
+
+/// An empty response with `status`.
+fn status<B>(status: StatusCode) -> Response<SanitizedBody<B>> {
+    let mut response = Response::new(Either::Right(Full::default()));
+    *response.status_mut() = status;
+    response
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for SanitizeService<S>
+where
+    S: Service<Request<SanitizedBody<ReqBody>>, Response = Response<ResBody>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    ReqBody: Body<Data = Bytes> + Send + 'static,
+    ReqBody::Error: Into<BoxError>,
+    ResBody: Body<Data = Bytes> + Send + 'static,
+    ResBody::Error: Into<BoxError>,
+{
+    type Response = Response<SanitizedBody<ResBody>>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        // The clone might not be ready, so keep it and call the one that is.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let kind = config
+                .requests
+                .then(|| config.kind(req.headers()))
+                .flatten();
+            let req = match kind {
+                Some(_) if is_encoded(req.headers()) => {
+                    return Ok(status(StatusCode::UNSUPPORTED_MEDIA_TYPE))
+                }
+                Some(kind) => {
+                    let (mut parts, body) = req.into_parts();
+                    match config.buffer(kind, &mut parts.headers, body).await {
+                        Ok(body) => Request::from_parts(parts, Either::Right(body)),
+                        Err(BufferError::TooLarge) => {
+                            return Ok(status(StatusCode::PAYLOAD_TOO_LARGE))
+                        }
+                        Err(BufferError::Read) => return Ok(status(StatusCode::BAD_REQUEST)),
+                    }
+                }
+                None => req.map(Either::Left),
+            };
+
+            let res = inner.call(req).await?;
+
+            let kind = config
+                .responses
+                .then(|| config.kind(res.headers()))
+                .flatten()
+                .filter(|_| !is_encoded(res.headers()));
+            Ok(match kind {
+                Some(kind) => {
+                    let (mut parts, body) = res.into_parts();
+                    match config.buffer(kind, &mut parts.headers, body).await {
+                        Ok(body) => Response::from_parts(parts, Either::Right(body)),
+                        Err(_) => status(StatusCode::BAD_GATEWAY),
+                    }
+                }
+                None => res.map(Either::Left),
+            })
+        })
+    }
+}
+
+// The assertions are on exact output, which differs with verbose markers
+#[cfg(all(test, not(feature = "verbose")))]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    /// Echo the request body and content headers back in the response.
+    async fn echo(
+        req: Request<SanitizedBody<Full<Bytes>>>,
+    ) -> Result<Response<Full<Bytes>>, Infallible> {
+        let mut res = Response::builder();
+        for header in [CONTENT_TYPE, CONTENT_ENCODING] {
+            if let Some(value) = req.headers().get(&header) {
+                res = res.header(header, value);
+            }
+        }
+        let body = req.into_body().collect().await.unwrap().to_bytes();
+        Ok(res.body(Full::new(body)).unwrap())
+    }
+
+    async fn send(
+        layer: &SanitizeLayer,
+        content_type: &str,
+        body: impl Into<Bytes>,
+    ) -> (HeaderMap, Bytes) {
+        let req = Request::builder()
+            .header(CONTENT_TYPE, content_type)
+            .body(Full::new(body.into()))
+            .unwrap();
+        let res = layer.layer(service_fn(echo)).oneshot(req).await.unwrap();
+        let (parts, body) = res.into_parts();
+        (parts.headers, body.collect().await.unwrap().to_bytes())
+    }
+
+    async fn send_request(layer: &SanitizeLayer, req: Request<Full<Bytes>>) -> StatusCode {
+        let res = layer.layer(service_fn(echo)).oneshot(req).await.unwrap();
+        res.status()
+    }
+
+    #[tokio::test]
+    async fn test_sanitize_layer() {
+        let layer = SanitizeLayer::default();
+
+        let (headers, body) = send(&layer, "text/plain; charset=utf-8", "hi🏴 there🏴!").await;
+        assert_eq!(body, "hi!");
+        assert_eq!(headers[CONTENT_LENGTH], "3");
+
+        // Invalid UTF-8 is converted lossily and U+FFFD is not allowed
+        let (_, body) = send(&layer, "TEXT/PLAIN", &b"hi\xFF"[..]).await;
+        assert_eq!(body, "hi");
+
+        // Not in the allowlist
+        let (_, body) = send(&layer, "text/markdown", "hi🏴").await;
+        assert_eq!(body, "hi🏴");
+
+        let layer = layer.content_types(["text/markdown", "application/problem+json"]);
+        let (_, body) = send(&layer, "text/markdown", "hi🏴").await;
+        assert_eq!(body, "hi");
+
+        // JSON is sanitized per string and clean JSON is left as-is
+        let json = r#"{"a🏴": ["b🏴 ", 1]}"#;
+        let (_, body) = send(&layer, "application/problem+json", json).await;
+        assert_eq!(body, r#"{"a":["b ",1]}"#);
+        let (_, body) = send(&layer, "application/problem+json", r#"{ "a": 1 }"#).await;
+        assert_eq!(body, r#"{ "a": 1 }"#);
+
+        // Only the echoed response is sanitized
+        let layer = layer.requests(false);
+        let (_, body) = send(&layer, "text/markdown", "hi🏴").await;
+        assert_eq!(body, "hi");
+        let layer = layer.responses(false);
+        let (_, body) = send(&layer, "text/markdown", "hi🏴").await;
+        assert_eq!(body, "hi🏴");
+    }
+
+    #[tokio::test]
+    async fn test_content_encoding() {
+        let layer = SanitizeLayer::default();
+        // Not valid UTF-8, as compressed bodies usually aren't
+        let gzipped = &b"\x1F\x8B\x08\x00\xFF\xFE"[..];
+        let request = |encoding: &str| {
+            Request::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_ENCODING, encoding)
+                .body(Full::new(Bytes::from_static(gzipped)))
+                .unwrap()
+        };
+        for encoding in ["gzip", "br", "identity, gzip"] {
+            // Compressed requests aren't forwarded, so they can't bypass it
+            let forwarded = Arc::new(AtomicBool::new(false));
+            let service = service_fn({
+                let forwarded = forwarded.clone();
+                move |req| {
+                    forwarded.store(true, Ordering::Relaxed);
+                    echo(req)
+                }
+            });
+            let res = layer
+                .layer(service)
+                .oneshot(request(encoding))
+                .await
+                .unwrap();
+            assert_eq!(
+                res.status(),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "{encoding}"
+            );
+            assert!(!forwarded.load(Ordering::Relaxed), "{encoding}");
+
+            // But compressed responses pass through
+            let layer = layer.clone().requests(false);
+            let res = layer
+                .layer(service_fn(echo))
+                .oneshot(request(encoding))
+                .await
+                .unwrap();
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(body, gzipped, "{encoding}");
+        }
+        // As do compressed requests that wouldn't be sanitized anyway
+        let req = Request::builder()
+            .header(CONTENT_TYPE, "image/png")
+            .header(CONTENT_ENCODING, "gzip")
+            .body(Full::new(Bytes::from_static(gzipped)))
+            .unwrap();
+        assert_eq!(send_request(&layer, req).await, StatusCode::OK);
+
+        let req = Request::builder()
+            .header(CONTENT_TYPE, "text/plain")
+            .header(CONTENT_ENCODING, "Identity")
+            .body(Full::new(Bytes::from_static("hi🏴".as_bytes())))
+            .unwrap();
+        let res = layer.layer(service_fn(echo)).oneshot(req).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "hi");
+    }
+
+    #[tokio::test]
+    async fn test_max_body_size() {
+        let layer = SanitizeLayer::default().max_body_size(4);
+        let (_, body) = send(&layer, "text/plain", "hi🏴").await;
+        assert_eq!(body, "");
+        let (_, body) = send(&layer, "text/plain", "four").await;
+        assert_eq!(body, "four");
+
+        let request = |body: &'static str, len: Option<usize>| {
+            let mut req = Request::builder().header(CONTENT_TYPE, "text/plain");
+            if let Some(len) = len {
+                req = req.header(CONTENT_LENGTH, len);
+            }
+            req.body(Full::new(Bytes::from_static(body.as_bytes())))
+                .unwrap()
+        };
+        let status = send_request(&layer, request("hello", None)).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        // Rejected by its length, without reading it
+        let status = send_request(&layer, request("hi", Some(1 << 20))).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        let status = send_request(&layer, request("hi", Some(2))).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // A large response is replaced, since it can't be sanitized
+        let layer = layer.requests(false);
+        let status = send_request(&layer, request("hello", None)).await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+    }
+}
@@ -0,0 +1,785 @@
+/// Runtime sanitization policies.
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::BTreeMap,
+    ops::RangeInclusive,
+    sync::{Arc, OnceLock, PoisonError, RwLock},
+};
+
+use crate::{
+    emoji::{classify_emoji, is_joiner, EmojiKind},
+    ranges::{languages::Lang, ENABLED_RANGES},
+    san::FORBIDDEN_EMOJI,
+    Error,
+};
+
+/// Policy set by [`set_default_policy`], if any.
+static DEFAULT: OnceLock<Policy> = OnceLock::new();
+
+/// Policies registered with [`register_policy`], by name.
+static NAMED: RwLock<BTreeMap<Cow<'static, str>, Arc<Policy>>> = RwLock::new(BTreeMap::new());
+
+thread_local! {
+    /// Policy set by [`with_policy`] for the current thread, if any.
+    static SCOPED: RefCell<Option<Arc<Policy>>> = const { RefCell::new(None) };
+}
+
+/// What sanitization should do with a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decision {
+    /// Keep the character.
+    Allow,
+    /// Remove the character. Like any invalid character, everything between
+    /// the first and last removed characters is removed as well.
+    Remove,
+    /// Substitute the character with another. The replacement is not checked.
+    Replace(char),
+}
+
+/// What [`Policy::emoji`] does with emoji, whatever blocks they are in. Which
+/// characters are emoji is decided by [`classify_emoji`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmojiPolicy {
+    /// Allow pictographs, skin tone modifiers and flags. Zero width joiners,
+    /// variation selectors and the keycap mark are invisible outside of a
+    /// sequence, so they are left to the policy, which allows them only
+    /// within one with the `emoji-full` feature.
+    Allow,
+    /// Allow only pictographs shown as text by default, such as © and ™, and
+    /// remove the rest.
+    Text,
+    /// Remove all pictographs and their components.
+    Remove,
+}
+
+/// Returns true if `c` is in the tag block, which can smuggle invisible ASCII,
+/// and is removed whatever the policy unless the `dangerous-allow-tags`
+/// feature is enabled. This guards against a policy allowing the block by
+/// accident, such as with custom ranges from `LANGSAN_RANGES_JSON`.
+pub(crate) const fn is_guarded(c: char) -> bool {
+    !cfg!(feature = "dangerous-allow-tags") && matches!(c as u32, 0xE0000..=0xE007F)
+}
+
+/// Classify `c` with `policy`, removing it if it [`is_guarded`].
+pub(crate) fn classify_guarded<P>(policy: &P, c: char) -> Decision
+where
+    P: CharPolicy + ?Sized,
+{
+    if is_guarded(c) {
+        Decision::Remove
+    } else {
+        policy.classify(c)
+    }
+}
+
+/// How removed text is marked in sanitized output, so a model can see that
+/// its input was sanitized. The [`Default`] is [`Marker::Bytes`] with the
+/// `verbose` feature, otherwise [`Marker::None`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Marker {
+    /// Removed text leaves no trace.
+    None,
+    /// `[N BYTES SANITIZED]`, where `N` is the number of bytes removed.
+    Bytes,
+    /// A fixed string, such as `[REMOVED]`.
+    Text(Cow<'static, str>),
+}
+
+/// The [`Default`] marker, for [`CharPolicy::marker`].
+static DEFAULT_MARKER: Marker = if cfg!(feature = "verbose") {
+    Marker::Bytes
+} else {
+    Marker::None
+};
+
+impl Default for Marker {
+    fn default() -> Self {
+        DEFAULT_MARKER.clone()
+    }
+}
+
+impl Marker {
+    /// Push the marker for `removed` bytes onto `out`.
+    pub(crate) fn push(&self, removed: usize, out: &mut String) {
+        match self {
+            Marker::None => {}
+            Marker::Bytes => out.push_str(&format!("[{removed} BYTES SANITIZED]")),
+            Marker::Text(text) => out.push_str(text),
+        }
+    }
+}
+
+/// Decides what happens to each character during sanitization. Implement this
+/// for domain-specific rules and pass it to [`sanitize_with`].
+///
+/// This is implemented for closures taking a `char` and returning a
+/// [`Decision`].
+///
+/// [`sanitize_with`]: crate::sanitize_with
+pub trait CharPolicy {
+    /// Classify a single character.
+    fn classify(&self, c: char) -> Decision;
+
+    /// How removed text is marked. This is the [`Default`] [`Marker`] unless
+    /// overridden.
+    fn marker(&self) -> &Marker {
+        &DEFAULT_MARKER
+    }
+}
+
+impl<F> CharPolicy for F
+where
+    F: Fn(char) -> Decision,
+{
+    fn classify(&self, c: char) -> Decision {
+        self(c)
+    }
+}
+
+/// A set of rules deciding which characters survive sanitization.
+///
+/// The [`Default`] policy allows the [`ENABLED_RANGES`] selected by feature,
+/// minus a few known-problematic characters. A `Policy` can only be as
+/// permissive as the ranges it's given, so it can be used to tighten
+/// sanitization at runtime, for example for untrusted routes.
+///
+/// Policies can be composed with [`union`], [`intersect`] and [`chain`], and
+/// may wrap any [`CharPolicy`] with [`Policy::custom`].
+///
+/// [`union`]: Policy::union
+/// [`intersect`]: Policy::intersect
+/// [`chain`]: Policy::chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    rules: Rules,
+    forbidden: Cow<'static, [char]>,
+    marker: Marker,
+}
+
+#[derive(Clone)]
+enum Rules {
+    Ranges(Cow<'static, [RangeInclusive<u32>]>),
+    Custom(Arc<dyn CharPolicy + Send + Sync>),
+    Union(Box<(Policy, Policy)>),
+    Intersect(Box<(Policy, Policy)>),
+    Chain(Box<(Policy, Policy)>),
+}
+
+impl Policy {
+    /// Create a policy allowing only the given `ranges`. Known-problematic
+    /// characters are still forbidden.
+    pub fn new(ranges: impl Into<Cow<'static, [RangeInclusive<u32>]>>) -> Self {
+        Self {
+            rules: Rules::Ranges(ranges.into()),
+            forbidden: Cow::Borrowed(FORBIDDEN_EMOJI),
+            marker: Marker::default(),
+        }
+    }
+
+    /// Create a policy allowing only the ranges of the given compiled-in
+    /// `languages`, so one binary can serve several locales, each with its own
+    /// policy. Known-problematic characters are still forbidden.
+    ///
+    /// ```
+    /// use langsan::{Lang, Policy};
+    ///
+    /// // Choose a policy for the locale of a request
+    /// let locales = ["german", "russian"];
+    /// let languages: Vec<Lang> = Lang::ALL
+    ///     .iter()
+    ///     .copied()
+    ///     .filter(|lang| locales.contains(&lang.name()))
+    ///     .collect();
+    /// let policy = Policy::for_languages(&languages);
+    /// assert!(!policy.is_allowed('\u{E0041}'));
+    /// ```
+    pub fn for_languages(languages: &[Lang]) -> Self {
+        let mut ranges = Vec::new();
+        for range in languages.iter().flat_map(|lang| lang.ranges()) {
+            if !ranges.contains(range) {
+                ranges.push(range.clone());
+            }
+        }
+        Self::new(ranges)
+    }
+
+    /// Create a policy allowing everything except `chars`. This is mostly
+    /// useful as a denylist to [`intersect`] with other policies.
+    ///
+    /// [`intersect`]: Policy::intersect
+    pub fn deny(chars: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            rules: Rules::Ranges(Cow::Borrowed(&[0..=(char::MAX as u32)])),
+            forbidden: Cow::Borrowed(&[]),
+            marker: Marker::default(),
+        }
+        .forbid(chars)
+    }
+
+    /// Create a policy from any [`CharPolicy`], such as a closure, so it can
+    /// be composed and used with [`with_policy`] or [`set_default_policy`].
+    pub fn custom(policy: impl CharPolicy + Send + Sync + 'static) -> Self {
+        Self {
+            rules: Rules::Custom(Arc::new(policy)),
+            forbidden: Cow::Borrowed(&[]),
+            marker: Marker::default(),
+        }
+    }
+
+    /// Additionally forbid `chars`, even if they are otherwise allowed.
+    pub fn forbid(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.forbidden.to_mut().extend(chars);
+        self
+    }
+
+    /// Decide emoji by their Unicode properties with `emoji`, rather than by
+    /// the blocks the policy allows, since pictographs are spread over many
+    /// blocks and plain symbols share blocks with them. Characters that
+    /// aren't emoji are decided as before, and forbidden characters stay
+    /// forbidden.
+    ///
+    /// ```
+    /// use langsan::{sanitize_with, EmojiPolicy, Policy};
+    ///
+    /// let policy = Policy::new(&[0x20..=0x7E]).verbose(false);
+    /// let emoji = policy.clone().emoji(EmojiPolicy::Allow);
+    /// assert_eq!(sanitize_with("hi 👋🏽⌚", &emoji), None);
+    /// let text = Policy::default().verbose(false).emoji(EmojiPolicy::Text);
+    /// assert_eq!(sanitize_with("hi 😀", &text).as_deref(), Some("hi "));
+    /// ```
+    pub fn emoji(self, emoji: EmojiPolicy) -> Self {
+        let forbidden = self.forbidden.clone();
+        let mut policy = match emoji {
+            EmojiPolicy::Allow => self.union(Policy::custom(|c: char| match classify_emoji(c) {
+                Some(_) if is_joiner(c) => Decision::Remove,
+                Some(_) => Decision::Allow,
+                None => Decision::Remove,
+            })),
+            EmojiPolicy::Text => {
+                self.intersect(Policy::custom(|c: char| match classify_emoji(c) {
+                    Some(EmojiKind::Emoji | EmojiKind::Component) => Decision::Remove,
+                    _ => Decision::Allow,
+                }))
+            }
+            EmojiPolicy::Remove => {
+                self.intersect(Policy::custom(|c: char| match classify_emoji(c) {
+                    Some(_) => Decision::Remove,
+                    None => Decision::Allow,
+                }))
+            }
+        };
+        policy.forbidden = forbidden;
+        policy
+    }
+
+    /// Mark removed text with `marker`, overriding the `verbose` feature.
+    ///
+    /// ```
+    /// use langsan::{sanitize_with, Marker, Policy};
+    ///
+    /// let policy = Policy::default().with_marker(Marker::Text("[REMOVED]".into()));
+    /// assert_eq!(sanitize_with("hi🏴", &policy).as_deref(), Some("hi[REMOVED]"));
+    /// let quiet = policy.verbose(false);
+    /// assert_eq!(sanitize_with("hi🏴", &quiet).as_deref(), Some("hi"));
+    /// ```
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Mark removed text with [`Marker::Bytes`] if `verbose`, otherwise not
+    /// at all, as the `verbose` feature does at compile time.
+    pub fn verbose(self, verbose: bool) -> Self {
+        self.with_marker(if verbose { Marker::Bytes } else { Marker::None })
+    }
+
+    /// A policy allowing characters allowed by either `self` or `other`. If
+    /// neither allows a character, a replacement is preferred to a removal.
+    /// Removed text is marked as `self` marks it.
+    pub fn union(self, other: Policy) -> Self {
+        let marker = self.marker.clone();
+        Self::composite(Rules::Union(Box::new((self, other))), marker)
+    }
+
+    /// A policy allowing only characters allowed by both `self` and `other`. A
+    /// removal by either wins, otherwise the first replacement is used.
+    /// Removed text is marked as `self` marks it.
+    pub fn intersect(self, other: Policy) -> Self {
+        let marker = self.marker.clone();
+        Self::composite(Rules::Intersect(Box::new((self, other))), marker)
+    }
+
+    /// A policy applying `self` and then `other` to the result. Unlike
+    /// [`intersect`], characters replaced by `self` are classified by `other`
+    /// as their replacement. Removed text is marked as `self` marks it.
+    ///
+    /// [`intersect`]: Policy::intersect
+    pub fn chain(self, other: Policy) -> Self {
+        let marker = self.marker.clone();
+        Self::composite(Rules::Chain(Box::new((self, other))), marker)
+    }
+
+    fn composite(rules: Rules, marker: Marker) -> Self {
+        Self {
+            rules,
+            forbidden: Cow::Borrowed(&[]),
+            marker,
+        }
+    }
+
+    /// Returns true if `c` survives sanitization unchanged under this policy.
+    pub fn is_allowed(&self, c: char) -> bool {
+        self.classify(c) == Decision::Allow
+    }
+}
+
+impl CharPolicy for Policy {
+    fn classify(&self, c: char) -> Decision {
+        if self.forbidden.contains(&c) || is_guarded(c) {
+            return Decision::Remove;
+        }
+
+        match &self.rules {
+            Rules::Ranges(ranges) => {
+                if ranges.iter().any(|range| range.contains(&(c as u32))) {
+                    Decision::Allow
+                } else {
+                    Decision::Remove
+                }
+            }
+            Rules::Custom(policy) => policy.classify(c),
+            Rules::Union(pair) => match (pair.0.classify(c), pair.1.classify(c)) {
+                (Decision::Allow, _) | (_, Decision::Allow) => Decision::Allow,
+                (Decision::Replace(r), _) | (_, Decision::Replace(r)) => Decision::Replace(r),
+                _ => Decision::Remove,
+            },
+            Rules::Intersect(pair) => match (pair.0.classify(c), pair.1.classify(c)) {
+                (Decision::Remove, _) | (_, Decision::Remove) => Decision::Remove,
+                (Decision::Replace(r), _) | (_, Decision::Replace(r)) => Decision::Replace(r),
+                _ => Decision::Allow,
+            },
+            Rules::Chain(pair) => match pair.0.classify(c) {
+                Decision::Allow => pair.1.classify(c),
+                Decision::Replace(r) => match pair.1.classify(r) {
+                    Decision::Allow => Decision::Replace(r),
+                    decision => decision,
+                },
+                Decision::Remove => Decision::Remove,
+            },
+        }
+    }
+
+    fn marker(&self) -> &Marker {
+        &self.marker
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new(ENABLED_RANGES)
+    }
+}
+
+impl std::fmt::Debug for Rules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rules::Ranges(ranges) => f.debug_tuple("Ranges").field(ranges).finish(),
+            Rules::Custom(_) => f.write_str("Custom(..)"),
+            Rules::Union(pair) => f
+                .debug_tuple("Union")
+                .field(&pair.0)
+                .field(&pair.1)
+                .finish(),
+            Rules::Intersect(pair) => f
+                .debug_tuple("Intersect")
+                .field(&pair.0)
+                .field(&pair.1)
+                .finish(),
+            Rules::Chain(pair) => f
+                .debug_tuple("Chain")
+                .field(&pair.0)
+                .field(&pair.1)
+                .finish(),
+        }
+    }
+}
+
+impl PartialEq for Rules {
+    /// Custom policies are only equal if they are the same instance.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Rules::Ranges(a), Rules::Ranges(b)) => a == b,
+            (Rules::Custom(a), Rules::Custom(b)) => Arc::ptr_eq(a, b),
+            (Rules::Union(a), Rules::Union(b))
+            | (Rules::Intersect(a), Rules::Intersect(b))
+            | (Rules::Chain(a), Rules::Chain(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Run `f` with `policy` as the active policy on the current thread. Anything
+/// sanitized inside `f` without an explicit policy, including [`CowStr`]
+/// construction, uses `policy`. The previous policy is restored when `f`
+/// returns or panics. Calls may be nested.
+///
+/// [`CowStr`]: crate::CowStr
+pub fn with_policy<R>(policy: Policy, f: impl FnOnce() -> R) -> R {
+    with_shared_policy(Arc::new(policy), f)
+}
+
+/// [`with_policy`] with a `policy` that's shared rather than moved.
+pub(crate) fn with_shared_policy<R>(policy: Arc<Policy>, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Arc<Policy>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SCOPED.with(|scoped| *scoped.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(SCOPED.with(|scoped| scoped.replace(Some(policy))));
+    f()
+}
+
+/// Set the process-wide default policy, used whenever no [`with_policy`]
+/// override is active. This is meant to be called once at startup. If the
+/// default has already been set, `policy` is returned as the error.
+pub fn set_default_policy(policy: Policy) -> Result<(), Policy> {
+    DEFAULT.set(policy)
+}
+
+/// Register `policy` under `name`, such as `"code"` or `"chat_input"`, for
+/// fields deriving [`Sanitize`] with `#[langsan(policy = "name")]` and for
+/// [`with_named_policy`]. This is meant to be called at startup, so policies
+/// are built once. Returns the policy previously registered under `name`, if
+/// any.
+///
+/// [`Sanitize`]: crate::Sanitize
+pub fn register_policy(name: impl Into<Cow<'static, str>>, policy: Policy) -> Option<Policy> {
+    NAMED
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name.into(), Arc::new(policy))
+        .map(Arc::unwrap_or_clone)
+}
+
+/// [`with_policy`] with the policy registered under `name` with
+/// [`register_policy`]. Returns an [`Error::InvalidPolicy`] if there is none.
+///
+/// ```
+/// use langsan::{register_policy, sanitize, with_named_policy, Policy};
+///
+/// register_policy("no_markup", Policy::default().forbid(['<', '>']).verbose(false));
+/// let s = with_named_policy("no_markup", || sanitize("a <b>"));
+/// assert_eq!(s.unwrap().as_deref(), Some("a "));
+/// assert!(with_named_policy("unknown", || ()).is_err());
+/// ```
+pub fn with_named_policy<R>(name: &str, f: impl FnOnce() -> R) -> Result<R, Error> {
+    let policy = NAMED
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name)
+        .cloned()
+        .ok_or_else(|| Error::InvalidPolicy(format!("no policy is registered as {name:?}")))?;
+    Ok(with_shared_policy(policy, f))
+}
+
+/// Returns true if sanitizing with the active policy keeps `c` as is, for
+/// highlighters and validators that must agree with the sanitizer. Characters
+/// it replaces are not allowed.
+///
+/// Each character is decided by itself, so with the `emoji-full` feature, an
+/// emoji joiner is allowed here even though sanitizing removes it outside of
+/// an emoji sequence.
+///
+/// ```
+/// use langsan::is_char_allowed;
+///
+/// assert!(is_char_allowed('a'));
+/// assert!(!is_char_allowed('🏴'));
+/// ```
+pub fn is_char_allowed(c: char) -> bool {
+    with_current(|policy| is_char_allowed_with(c, policy))
+}
+
+/// [`is_char_allowed`] with an explicit `policy` rather than the active one.
+pub fn is_char_allowed_with<P>(c: char, policy: &P) -> bool
+where
+    P: CharPolicy + ?Sized,
+{
+    classify_guarded(policy, c) == Decision::Allow
+}
+
+/// Call `f` with the active policy for the current thread. This is the
+/// [`with_policy`] override if any, then the [`set_default_policy`] one, and
+/// otherwise [`Policy::default`].
+pub(crate) fn with_current<R>(f: impl FnOnce(&Policy) -> R) -> R {
+    // Not borrowed while `f` runs, since it may sanitize or set a policy too
+    let scoped = SCOPED.with(|scoped| scoped.borrow().clone());
+    match (scoped.as_deref(), DEFAULT.get()) {
+        (Some(policy), _) | (None, Some(policy)) => f(policy),
+        (None, None) => f(&Policy::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "verbose"))]
+    use crate::CowStr;
+    use crate::{
+        ranges::{BASIC_LATIN, WHITESPACE},
+        sanitize, sanitize_with,
+    };
+
+    const ASCII: &[RangeInclusive<u32>] = &[WHITESPACE, BASIC_LATIN];
+
+    #[test]
+    fn test_policy() {
+        let policy = Policy::default();
+        assert!(policy.is_allowed('a'));
+        assert!(!policy.is_allowed('🏴'));
+        assert!(!policy.is_allowed('\u{E0041}'));
+
+        let policy = Policy::new(ASCII).forbid(['x']);
+        assert!(policy.is_allowed('a'));
+        assert!(!policy.is_allowed('x'));
+        assert!(!policy.is_allowed('é'));
+    }
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_with_policy() {
+        let policy = Policy::new(ASCII).forbid(['!']);
+        let s = with_policy(policy.clone(), || {
+            // nested scopes override and then restore the outer one
+            let inner = with_policy(Policy::default(), || sanitize("Hello!"));
+            assert_eq!(inner, None);
+            CowStr::from("Hello, world!")
+        });
+        assert_eq!(s.as_ref(), "Hello, world");
+        // the override is gone outside of the scope
+        assert_eq!(sanitize("Hello, world!"), None);
+
+        // and is also restored if the closure panics
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            with_policy(policy, || panic!("oops"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(sanitize("Hello, world!"), None);
+    }
+
+    #[test]
+    fn test_with_policy_reentrant() {
+        // A policy that itself sanitizes, under another policy
+        let no_vowels = |c: char| {
+            let vowels = Policy::new(ASCII).forbid(['a', 'e', 'i', 'o', 'u']);
+            match with_policy(vowels, || sanitize(&c.to_string())) {
+                Some(_) => Decision::Remove,
+                None => Decision::Allow,
+            }
+        };
+        let policy = Policy::custom(no_vowels).verbose(false);
+        let s = with_policy(policy, || sanitize("bat🏴, bt"));
+        assert_eq!(s.as_deref(), Some("b, bt"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_named_policy() {
+        assert_eq!(register_policy("test_ascii", Policy::new(ASCII)), None);
+        let s = with_named_policy("test_ascii", || sanitize("é!"));
+        assert_eq!(s, Ok(Some("!".into())));
+        // Replacing a policy returns the old one
+        let exclaim = Policy::new(ASCII).forbid(['!']);
+        assert_eq!(
+            register_policy("test_ascii", exclaim),
+            Some(Policy::new(ASCII))
+        );
+        assert_eq!(
+            with_named_policy("test_ascii", || sanitize("a!")),
+            Ok(Some("a".into()))
+        );
+        assert_eq!(
+            with_named_policy("test_missing", || ()),
+            Err(Error::InvalidPolicy(
+                "no policy is registered as \"test_missing\"".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_set_default_policy() {
+        // Tests share the process, so this must not change behavior elsewhere.
+        assert!(set_default_policy(Policy::default()).is_ok());
+        assert_eq!(DEFAULT.get(), Some(&Policy::default()));
+        assert!(set_default_policy(Policy::new(ASCII)).is_err());
+        assert_eq!(DEFAULT.get(), Some(&Policy::default()));
+        // scoped overrides still take precedence
+        let s = with_policy(Policy::new(ASCII).forbid(['!']), || sanitize("Hi!"));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(s.as_deref(), Some("Hi"));
+        #[cfg(feature = "verbose")]
+        assert_eq!(s.as_deref(), Some("Hi[1 BYTES SANITIZED]"));
+    }
+
+    #[test]
+    fn test_for_languages() {
+        for &lang in Lang::ALL {
+            let policy = Policy::for_languages(&[lang]);
+            for range in lang.ranges() {
+                let (start, end) = (*range.start(), *range.end());
+                for c in [start, end].into_iter().filter_map(char::from_u32) {
+                    assert!(policy.is_allowed(c), "{}: U+{:04X}", lang.name(), c as u32);
+                }
+            }
+            assert!(!policy.is_allowed('🏴'));
+        }
+        assert!(!Policy::for_languages(&[]).is_allowed('a'));
+
+        #[cfg(all(feature = "german", feature = "russian"))]
+        {
+            let german = Policy::for_languages(&[Lang::German]);
+            assert!(german.is_allowed('ß'));
+            assert!(!german.is_allowed('Ж'));
+            let both = Policy::for_languages(&[Lang::German, Lang::Russian]);
+            assert!(both.is_allowed('ß'));
+            assert!(both.is_allowed('Ж'));
+            assert_eq!(Lang::Russian.name(), "russian");
+        }
+    }
+
+    #[test]
+    fn test_combinators() {
+        let digits = Policy::new(&[0x30..=0x39]);
+        let letters = Policy::custom(|c: char| match c {
+            'a'..='z' => Decision::Allow,
+            '_' => Decision::Replace('-'),
+            _ => Decision::Remove,
+        });
+
+        let union = digits.clone().union(letters.clone());
+        assert!(union.is_allowed('1'));
+        assert!(union.is_allowed('a'));
+        assert_eq!(union.classify('_'), Decision::Replace('-'));
+        assert_eq!(union.classify('!'), Decision::Remove);
+
+        let intersect = Policy::new(ASCII).intersect(Policy::deny(['a']));
+        assert!(intersect.is_allowed('b'));
+        assert!(!intersect.is_allowed('a'));
+        assert!(!intersect.is_allowed('é'));
+        assert_eq!(
+            Policy::new(ASCII).intersect(letters.clone()).classify('_'),
+            Decision::Replace('-')
+        );
+
+        // `-` is not a letter, so chaining removes what intersecting replaces
+        assert_eq!(
+            letters.clone().chain(letters.clone()).classify('_'),
+            Decision::Remove
+        );
+        assert_eq!(
+            letters.clone().chain(Policy::new(ASCII)).classify('_'),
+            Decision::Replace('-')
+        );
+        assert_eq!(
+            digits.clone().chain(letters.clone()).classify('1'),
+            Decision::Remove
+        );
+
+        // forbidding applies on top of composition
+        let union = union.forbid(['z']);
+        assert!(!union.is_allowed('z'));
+        assert_eq!(union, union.clone());
+        assert_ne!(union, letters);
+    }
+
+    #[test]
+    fn test_marker() {
+        assert_eq!(
+            Marker::default() == Marker::Bytes,
+            cfg!(feature = "verbose")
+        );
+        assert_eq!(Policy::default().marker(), &Marker::default());
+
+        let verbose = Policy::new(ASCII).verbose(true);
+        let quiet = Policy::new(ASCII).verbose(false);
+        assert_eq!(
+            sanitize_with("a\u{E0041}b", &verbose).as_deref(),
+            Some("a[4 BYTES SANITIZED]b")
+        );
+        assert_eq!(sanitize_with("a\u{E0041}b", &quiet).as_deref(), Some("ab"));
+        assert_ne!(verbose, quiet);
+
+        // Composites mark as their first policy does
+        let text = Policy::new(ASCII).with_marker(Marker::Text("<x>".into()));
+        let union = text.clone().union(verbose.clone());
+        assert_eq!(union.marker(), &Marker::Text("<x>".into()));
+        assert_eq!(
+            sanitize_with("é\u{E0041}", &union.chain(quiet)).as_deref(),
+            Some("<x>")
+        );
+
+        // and so does the active policy
+        with_policy(verbose, || {
+            assert_eq!(
+                sanitize("\u{E0041}").as_deref(),
+                Some("[4 BYTES SANITIZED]")
+            );
+        });
+    }
+
+    #[test]
+    fn test_tags_guarded() {
+        let tag = '\u{E0041}';
+        let allowed = !cfg!(feature = "dangerous-allow-tags");
+        let everything = |_| Decision::Allow;
+        assert_eq!(is_guarded(tag), allowed);
+        assert!(!is_guarded('a'));
+        assert_eq!(Policy::deny([]).is_allowed(tag), !allowed);
+        assert_eq!(Policy::custom(everything).is_allowed(tag), !allowed);
+        assert_eq!(Policy::new(&[0xE0000..=0xE007F]).is_allowed(tag), !allowed);
+        assert_eq!(sanitize_with("a\u{E0041}", &everything).is_some(), allowed);
+        assert_eq!(is_char_allowed_with(tag, &everything), !allowed);
+    }
+
+    #[test]
+    fn test_is_char_allowed() {
+        let tabs = |c: char| match c {
+            '\t' => Decision::Replace(' '),
+            'x' => Decision::Remove,
+            _ => Decision::Allow,
+        };
+        assert!(is_char_allowed_with('a', &tabs));
+        assert!(!is_char_allowed_with('\t', &tabs));
+        assert!(!is_char_allowed_with('x', &tabs));
+        with_policy(Policy::new(&[0x61..=0x7A]), || {
+            assert!(is_char_allowed('a'));
+            assert!(!is_char_allowed('A'));
+        });
+    }
+
+    #[test]
+    fn test_emoji() {
+        let allow = Policy::new(ASCII).emoji(EmojiPolicy::Allow);
+        for c in ['😀', '⌚', '©', '\u{1F3FD}', '\u{1F1FA}'] {
+            assert!(allow.is_allowed(c), "{c:?}");
+        }
+        assert!(!allow.is_allowed('⌘'));
+        assert!(!allow.is_allowed('\u{200D}'));
+        // Still forbidden
+        assert!(!allow.is_allowed('🏴'));
+        assert!(!allow.is_allowed('\u{E0041}'));
+
+        let everything = Policy::deny([]);
+        let text = everything.clone().emoji(EmojiPolicy::Text);
+        assert!(text.is_allowed('©') && text.is_allowed('⌘') && text.is_allowed('a'));
+        assert!(!text.is_allowed('😀') && !text.is_allowed('\u{FE0F}'));
+        let remove = everything.emoji(EmojiPolicy::Remove);
+        assert!(!remove.is_allowed('©') && !remove.is_allowed('\u{1F3FD}'));
+        assert!(remove.is_allowed('⌘') && remove.is_allowed('1'));
+    }
+}
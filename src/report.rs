@@ -0,0 +1,317 @@
+/// Reports describing what sanitization changed.
+#[cfg(feature = "serde")]
+use std::borrow::Cow;
+use std::{collections::BTreeMap, fmt, ops::Range};
+
+#[cfg(feature = "serde")]
+use crate::ranges::find_block;
+use crate::{analyze::risk_score, Severity};
+
+/// What sanitization changed in a string.
+///
+/// With the `serde` feature, this can be serialized for logs and telemetry.
+#[derive(Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    pub(crate) removed: Option<Range<usize>>,
+    pub(crate) replaced: usize,
+    /// The severity of the worst removed character, if any were removed.
+    pub(crate) severity: Option<Severity>,
+    /// The number of removed characters, by Unicode block.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_blocks")
+    )]
+    pub(crate) blocks: BTreeMap<&'static str, usize>,
+    /// The length of the longest run of removed characters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) max_run: usize,
+}
+
+impl Report {
+    /// The byte range of the input that was removed, if any. This spans from
+    /// the first to the last removed character, inclusive.
+    pub fn removed(&self) -> Option<Range<usize>> {
+        self.removed.clone()
+    }
+
+    /// The number of bytes of the input that were removed.
+    pub fn removed_bytes(&self) -> usize {
+        self.removed.as_ref().map(|r| r.len()).unwrap_or(0)
+    }
+
+    /// The number of characters outside the removed span that were replaced.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// How serious the change was. Removing tag characters or bidi controls is
+    /// [`Severity::High`] and other removals [`Severity::Medium`], while
+    /// replacements alone are [`Severity::Info`].
+    pub fn severity(&self) -> Severity {
+        self.severity.unwrap_or(Severity::Info)
+    }
+
+    /// The number of characters removed from each Unicode block, such as
+    /// `{"Tags": 3}`. Allowed characters between removed ones aren't counted.
+    pub fn removed_by_block(&self) -> &BTreeMap<&'static str, usize> {
+        &self.blocks
+    }
+
+    /// The length, in characters, of the longest run of consecutive removed
+    /// characters.
+    pub fn max_run(&self) -> usize {
+        self.max_run
+    }
+
+    /// A risk score from 0 to 1 for the change, as for an [`Analysis`].
+    ///
+    /// [`Analysis`]: crate::Analysis
+    pub fn risk_score(&self) -> f64 {
+        if self.is_empty() {
+            0.0
+        } else {
+            risk_score([self.severity()])
+        }
+    }
+
+    /// Returns true if nothing was changed.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_none() && self.replaced == 0
+    }
+
+    /// A unified diff, with three lines of context, from `original` to
+    /// `sanitized`, the output this report describes. For review tools and
+    /// test assertions. Given other strings, the diff is still correct, if
+    /// coarser, since lines can't be paired by the report.
+    ///
+    /// ```
+    /// use langsan::sanitize_outcome;
+    ///
+    /// let input = "one\ntwo🏴\nthree\n";
+    /// let outcome = sanitize_outcome(input);
+    /// let diff = outcome.report().unwrap().diff(input, outcome.as_str());
+    /// # #[cfg(not(feature = "verbose"))]
+    /// assert_eq!(
+    ///     diff,
+    ///     "--- original\n+++ sanitized\n@@ -1,3 +1,3 @@\n one\n-two🏴\n+two\n three\n"
+    /// );
+    /// ```
+    pub fn diff(&self, original: &str, sanitized: &str) -> String {
+        let old: Vec<&str> = original.split_inclusive('\n').collect();
+        let new: Vec<&str> = sanitized.split_inclusive('\n').collect();
+        let pairs = self.pair_lines(original, old.len(), new.len());
+        let changed: Vec<bool> = pairs
+            .iter()
+            .map(|(o, n)| old[o.clone()] != new[n.clone()])
+            .collect();
+
+        let mut diff = String::new();
+        let mut i = 0;
+        while let Some(first) = changed[i..].iter().position(|c| *c).map(|p| p + i) {
+            // Extend the hunk while the next change is close enough to share
+            // context with it.
+            let mut last = first;
+            while let Some(next) = changed[last + 1..].iter().position(|c| *c) {
+                if next > 2 * CONTEXT {
+                    break;
+                }
+                last += next + 1;
+            }
+            let start = first.saturating_sub(CONTEXT);
+            let end = (last + CONTEXT + 1).min(pairs.len());
+            if diff.is_empty() {
+                diff.push_str("--- original\n+++ sanitized\n");
+            }
+            let old_lines = pairs[start].0.start..pairs[end - 1].0.end;
+            let new_lines = pairs[start].1.start..pairs[end - 1].1.end;
+            diff.push_str(&format!(
+                "@@ -{} +{} @@\n",
+                hunk_range(old_lines),
+                hunk_range(new_lines)
+            ));
+            for ((o, n), changed) in pairs[start..end].iter().zip(&changed[start..end]) {
+                if *changed {
+                    push_lines(&mut diff, '-', &old[o.clone()]);
+                    push_lines(&mut diff, '+', &new[n.clone()]);
+                } else {
+                    push_lines(&mut diff, ' ', &old[o.clone()]);
+                }
+            }
+            i = end;
+        }
+        diff
+    }
+
+    /// Pair the ranges of lines in the original with the lines they became.
+    /// The lines the removed span covers became one line. If the lines don't
+    /// line up, such as when a policy replaces newlines, everything is paired.
+    fn pair_lines(
+        &self,
+        original: &str,
+        old: usize,
+        new: usize,
+    ) -> Vec<(Range<usize>, Range<usize>)> {
+        let (merged, merged_lines) = match &self.removed {
+            Some(removed) => match (original.get(..removed.start), original.get(removed.clone())) {
+                (Some(before), Some(span)) => {
+                    (before.matches('\n').count(), span.matches('\n').count())
+                }
+                // Not the input this report is for
+                _ => return vec![(0..old, 0..new)],
+            },
+            None => (old, 0),
+        };
+        let mut pairs = Vec::with_capacity(old.min(new));
+        let (mut i, mut j) = (0, 0);
+        while i < old {
+            let len = if i == merged { merged_lines + 1 } else { 1 };
+            let end = (i + len).min(old);
+            pairs.push((i..end, j..j + 1));
+            i = end;
+            j += 1;
+        }
+        if j == new {
+            pairs
+        } else {
+            vec![(0..old, 0..new)]
+        }
+    }
+}
+
+/// The lines of context around each hunk of a [`Report::diff`].
+const CONTEXT: usize = 3;
+
+/// A range of lines in a hunk header, one-based unless it's empty.
+fn hunk_range(lines: Range<usize>) -> String {
+    match lines.len() {
+        0 => format!("{},0", lines.start),
+        len => format!("{},{len}", lines.start + 1),
+    }
+}
+
+/// Push each of `lines` onto `diff` after `prefix`.
+fn push_lines(diff: &mut String, prefix: char, lines: &[&str]) {
+    for line in lines {
+        diff.push(prefix);
+        diff.push_str(line.strip_suffix('\n').unwrap_or(line));
+        diff.push('\n');
+    }
+}
+
+/// Deserialize block names as the names [`block_name`] returns, so they can
+/// stay `'static`. Unknown names are an error.
+///
+/// [`block_name`]: crate::ranges::block_name
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_blocks<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<&'static str, usize>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    BTreeMap::<Cow<'de, str>, usize>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, count)| match find_block(&name) {
+            Some(block) => Ok((block, count)),
+            None => Err(D::Error::custom(format!("unknown block: {name}"))),
+        })
+        .collect()
+}
+
+/// The removed characters by block of all `reports`, combined.
+pub(crate) fn all_blocks<'r>(
+    reports: impl IntoIterator<Item = &'r Report>,
+) -> BTreeMap<&'static str, usize> {
+    let mut blocks = BTreeMap::new();
+    for (block, count) in reports.into_iter().flat_map(|r| &r.blocks) {
+        *blocks.entry(*block).or_default() += count;
+    }
+    blocks
+}
+
+impl fmt::Debug for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Report")
+            .field("modified", &!self.is_empty())
+            .field("removed", &self.removed)
+            .field("removed_bytes", &self.removed_bytes())
+            .field("replaced", &self.replaced)
+            .field("severity", &self.severity())
+            .field("max_run", &self.max_run)
+            .field("blocks", &self.blocks)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{policy::Decision, sanitize_outcome_with, Policy};
+
+    fn diff<P: crate::CharPolicy>(s: &str, policy: &P) -> String {
+        let outcome = sanitize_outcome_with(s, policy);
+        outcome.report().unwrap().diff(s, outcome.as_str())
+    }
+
+    #[test]
+    fn test_diff_mismatched() {
+        let input = "one\ntwo🏴\nthree\n";
+        let outcome = sanitize_outcome_with(input, &Policy::default());
+        let report = outcome.report().unwrap();
+        // The removed span is past the end, or inside a character
+        assert_eq!(report.diff("one\n", "one\n"), "");
+        assert_eq!(
+            report.diff("one\nt🏴\n", "one\n"),
+            "--- original\n+++ sanitized\n@@ -1,2 +1,1 @@\n-one\n-t🏴\n+one\n"
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let outcome = sanitize_outcome_with("a\u{E0041}\u{E0042}b\u{E0043}", &Policy::default());
+        let debug = format!("{:?}", outcome.report().unwrap());
+        assert!(debug.contains("max_run: 2"), "{debug}");
+    }
+
+    #[test]
+    fn test_diff() {
+        // Far apart changes are separate hunks
+        let tabs = |c: char| match c {
+            '\t' => Decision::Replace(' '),
+            _ => Decision::Allow,
+        };
+        let s = "a\t\nb\nc\nd\ne\nf\ng\nh\ni\nj\t\n";
+        assert_eq!(
+            diff(s, &tabs),
+            "--- original\n+++ sanitized\n\
+             @@ -1,4 +1,4 @@\n-a\t\n+a \n b\n c\n d\n\
+             @@ -7,4 +7,4 @@\n g\n h\n i\n-j\t\n+j \n"
+        );
+        // Close changes share a hunk
+        assert_eq!(
+            diff("a\t\nb\nc\t", &tabs),
+            "--- original\n+++ sanitized\n@@ -1,3 +1,3 @@\n-a\t\n+a \n b\n-c\t\n+c \n"
+        );
+        let outcome = sanitize_outcome_with(s, &tabs);
+        assert_eq!(outcome.report().unwrap().diff(s, s), "");
+
+        // Lines in the removed span become one line
+        let quiet = Policy::new(&[0x0A..=0x0A, 0x20..=0x7E]).verbose(false);
+        assert_eq!(
+            diff("x\na🏴\n🏴b\ny", &quiet),
+            "--- original\n+++ sanitized\n@@ -1,4 +1,3 @@\n x\n-a🏴\n-🏴b\n+ab\n y\n"
+        );
+
+        // Replacing newlines falls back to one hunk
+        let spaces = |c: char| match c {
+            '\n' => Decision::Replace(' '),
+            _ => Decision::Allow,
+        };
+        assert_eq!(
+            diff("a\nb", &spaces),
+            "--- original\n+++ sanitized\n@@ -1,2 +1,1 @@\n-a\n-b\n+a b\n"
+        );
+    }
+}
@@ -1,12 +1,28 @@
 /// Sanitization functions for crate string types.
-use crate::ranges::ENABLED_RANGES;
+use std::{borrow::Cow, collections::BTreeMap};
 
-const FORBIDDEN_EMOJI: &[char] = &['🏴'];
+use crate::{
+    analyze::removal_severity,
+    emoji,
+    policy::{classify_guarded, with_current, CharPolicy, Decision, Marker},
+    ranges::block_name,
+    Error, Policy, Report,
+};
+
+pub(crate) const FORBIDDEN_EMOJI: &[char] = &['🏴'];
 
 /// Return `Some(string)` if the input `&str` has been sanitized, otherwise
 /// `None`. Sanitization is performed by removing any characters that are not in
 /// the enabled [`RANGES`] and then **removing any charachters in between the
 /// first invalid character and the last invalid character**.
+///
+/// The active [`Policy`] is used, which is the [`Default`] one unless
+/// overridden with [`set_default_policy`] or [`with_policy`].
+///
+/// [`RANGES`]: crate::ENABLED_RANGES
+/// [`Policy`]: crate::Policy
+/// [`set_default_policy`]: crate::set_default_policy
+/// [`with_policy`]: crate::with_policy
 //
 // This is pretty aggressive, but also very simple. It's not perfect, but it
 // should be good enough for most use cases. It attempts to cover all cases
@@ -18,46 +34,435 @@ const FORBIDDEN_EMOJI: &[char] = &['🏴'];
 // possible to the chat agent so they can ask the user for clarification if
 // necessary.
 pub fn sanitize(s: &str) -> Option<String> {
+    with_current(|policy| sanitize_with(s, policy))
+}
+
+/// [`sanitize`] with an explicit `policy` rather than the active one. This may
+/// be a [`Policy`] or any other [`CharPolicy`]. Characters the policy
+/// [`Decision::Replace`]s outside the removed span are substituted as-is.
+///
+/// [`Policy`]: crate::Policy
+pub fn sanitize_with<P>(s: &str, policy: &P) -> Option<String>
+where
+    P: CharPolicy + ?Sized,
+{
+    match sanitize_outcome_with(s, policy) {
+        Outcome::Clean(_) => None,
+        Outcome::Modified { output, .. } => Some(output),
+    }
+}
+
+/// The result of [`sanitize_outcome`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<'a> {
+    /// The input was not modified and is borrowed as-is.
+    Clean(&'a str),
+    /// The input was modified. The `report` describes how.
+    Modified { output: String, report: Report },
+}
+
+impl<'a> Outcome<'a> {
+    /// The sanitized string.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Outcome::Clean(s) => s,
+            Outcome::Modified { output, .. } => output,
+        }
+    }
+
+    /// Returns true if the input was not modified.
+    pub fn is_clean(&self) -> bool {
+        matches!(self, Outcome::Clean(_))
+    }
+
+    /// The [`Report`] if the input was modified.
+    pub fn report(&self) -> Option<&Report> {
+        match self {
+            Outcome::Clean(_) => None,
+            Outcome::Modified { report, .. } => Some(report),
+        }
+    }
+
+    /// The sanitized string, borrowed if it was clean.
+    pub fn into_cow(self) -> Cow<'a, str> {
+        match self {
+            Outcome::Clean(s) => Cow::Borrowed(s),
+            Outcome::Modified { output, .. } => Cow::Owned(output),
+        }
+    }
+}
+
+/// Sanitize `s` with the active policy into both a quiet form, for the model,
+/// and a marked form, for logs, scanning it only once. The quiet form has no
+/// markers, and is borrowed if nothing was changed. The marked form uses the
+/// policy's marker, or [`Marker::Bytes`] if it has none.
+///
+/// ```
+/// let (quiet, marked) = langsan::sanitize_both("Hi🏴!");
+/// assert_eq!(quiet, "Hi!");
+/// assert_eq!(marked, "Hi[4 BYTES SANITIZED]!");
+/// ```
+pub fn sanitize_both(s: &str) -> (Cow<'_, str>, String) {
+    with_current(|policy| sanitize_both_with(s, policy))
+}
+
+/// [`sanitize_both`] with an explicit `policy` rather than the active one.
+pub fn sanitize_both_with<'a, P>(s: &'a str, policy: &P) -> (Cow<'a, str>, String)
+where
+    P: CharPolicy + ?Sized,
+{
+    let Some(report) = scan(s, policy) else {
+        return (Cow::Borrowed(s), s.to_string());
+    };
+    let mut quiet = String::with_capacity(s.len());
+    let at = render_marked(s, policy, &report, &Marker::None, &mut quiet);
+    // The marked form is the quiet one with the marker put back.
+    let marker = match policy.marker() {
+        Marker::None => &Marker::Bytes,
+        marker => marker,
+    };
+    let mut marked = String::with_capacity(quiet.len() + 24);
+    match (at, &report.removed) {
+        (Some(at), Some(span)) => {
+            marked.push_str(&quiet[..at]);
+            marker.push(span.len(), &mut marked);
+            marked.push_str(&quiet[at..]);
+        }
+        _ => marked.push_str(&quiet),
+    }
+    (Cow::Owned(quiet), marked)
+}
+
+/// Like [`sanitize`] but explicit about whether anything was changed, and
+/// how.
+pub fn sanitize_outcome(s: &str) -> Outcome<'_> {
+    with_current(|policy| sanitize_outcome_with(s, policy))
+}
+
+/// [`sanitize_outcome`] with an explicit `policy` rather than the active one.
+pub fn sanitize_outcome_with<'a, P>(s: &'a str, policy: &P) -> Outcome<'a>
+where
+    P: CharPolicy + ?Sized,
+{
+    match scan(s, policy) {
+        Some(report) => {
+            let mut output = String::with_capacity(s.len());
+            render(s, policy, &report, &mut output);
+            Outcome::Modified { output, report }
+        }
+        None => Outcome::Clean(s),
+    }
+}
+
+/// Decide what happens to the character `c` at byte `i` of `s`. This is the
+/// `policy`'s decision, except that with the `emoji-full` feature, emoji
+/// joiners outside of an emoji sequence are removed.
+pub(crate) fn decide<P>(s: &str, i: usize, c: char, policy: &P) -> Decision
+where
+    P: CharPolicy + ?Sized,
+{
+    match classify_guarded(policy, c) {
+        Decision::Allow
+            if cfg!(feature = "emoji-full")
+                && emoji::is_joiner(c)
+                && !emoji::in_sequence(s, i, c, |c| policy.classify(c) == Decision::Allow) =>
+        {
+            Decision::Remove
+        }
+        decision => decision,
+    }
+}
+
+/// Scan `s`, returning a [`Report`] if sanitization would modify it.
+pub(crate) fn scan<P>(s: &str, policy: &P) -> Option<Report>
+where
+    P: CharPolicy + ?Sized,
+{
     let mut first_invalid = None;
     let mut last_invalid = None;
+    let mut replaced = 0;
+    let mut severity = None;
+    let mut blocks = BTreeMap::new();
+    let mut run = 0;
+    let mut max_run = 0;
 
     for (i, c) in s.char_indices() {
-        if FORBIDDEN_EMOJI.contains(&c)
-            || !ENABLED_RANGES
-                .iter()
-                .any(|range| range.contains(&(c as u32)))
-        {
-            if let Some(_) = first_invalid {
-                last_invalid = Some(i);
-                continue;
-            } else {
-                first_invalid = Some(i);
+        match decide(s, i, c, policy) {
+            Decision::Allow => run = 0,
+            Decision::Remove => {
+                if first_invalid.is_none() {
+                    first_invalid = Some(i);
+                }
                 last_invalid = Some(i);
-                continue;
+                severity = severity.max(Some(removal_severity(c)));
+                *blocks.entry(block_name(c)).or_default() += 1;
+                run += 1;
+                max_run = max_run.max(run);
+            }
+            Decision::Replace(_) => {
+                replaced += 1;
+                run = 0;
             }
         }
     }
 
-    if let (Some(first), Some(last)) = (first_invalid, last_invalid) {
-        let begin = &s[..first];
+    let removed = if let (Some(first), Some(last)) = (first_invalid, last_invalid) {
         // Last is the next character after the last invalid character
         let last = last + s[last..].chars().next().map(|c| c.len_utf8()).unwrap_or(0);
-        let end = &s[last..];
-
-        let sanitized = if cfg!(feature = "verbose") {
-            format!("{}[{} BYTES SANITIZED]{}", begin, last - first, end)
-        } else {
-            format!("{}{}", begin, end)
-        };
-        Some(sanitized)
+        // Replacements inside the removed span don't count
+        replaced -= s[first..last]
+            .chars()
+            .filter(|c| matches!(classify_guarded(policy, *c), Decision::Replace(_)))
+            .count();
+        Some(first..last)
+    } else if replaced == 0 {
+        return None;
     } else {
         None
+    };
+
+    Some(Report {
+        removed,
+        replaced,
+        severity,
+        blocks,
+        max_run,
+    })
+}
+
+/// Sanitize `s` in place, using `scratch` as a reusable buffer. Returns true if
+/// `s` was modified.
+pub(crate) fn sanitize_in_place<P>(s: &mut String, policy: &P, scratch: &mut String) -> bool
+where
+    P: CharPolicy + ?Sized,
+{
+    match scan(s, policy) {
+        Some(report) => {
+            scratch.clear();
+            render(s, policy, &report, scratch);
+            // The old buffer becomes the scratch buffer for the next call.
+            std::mem::swap(s, scratch);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Append the sanitized version of `s` described by `report` to `out`,
+/// returning the offset in `out` of any removed text, before its marker.
+pub(crate) fn render<P>(s: &str, policy: &P, report: &Report, out: &mut String) -> Option<usize>
+where
+    P: CharPolicy + ?Sized,
+{
+    render_marked(s, policy, report, policy.marker(), out)
+}
+
+/// [`render`] with an explicit `marker` rather than the `policy`'s.
+fn render_marked<P>(
+    s: &str,
+    policy: &P,
+    report: &Report,
+    marker: &Marker,
+    out: &mut String,
+) -> Option<usize>
+where
+    P: CharPolicy + ?Sized,
+{
+    let replacing = report.replaced > 0;
+    match &report.removed {
+        Some(span) => {
+            debug_assert_kept(&s[..span.start], policy);
+            debug_assert_kept(&s[span.end..], policy);
+            push_replaced(out, &s[..span.start], policy, replacing);
+            let at = out.len();
+            marker.push(span.len(), out);
+            push_replaced(out, &s[span.end..], policy, replacing);
+            Some(at)
+        }
+        None => {
+            debug_assert_kept(s, policy);
+            push_replaced(out, s, policy, replacing);
+            None
+        }
+    }
+}
+
+/// Check, in debug builds, that nothing `policy` removes is in `kept`, the
+/// text sanitization keeps, so a bug in finding what to remove can't let it
+/// through. Replacements are allowed, since they are made as `kept` is copied.
+#[track_caller]
+fn debug_assert_kept<P>(kept: &str, policy: &P)
+where
+    P: CharPolicy + ?Sized,
+{
+    if cfg!(debug_assertions) {
+        if let Some((i, c)) = kept
+            .char_indices()
+            .find(|&(i, c)| decide(kept, i, c, policy) == Decision::Remove)
+        {
+            panic!(
+                "sanitization kept U+{:04X} at byte {i} of {kept:?}",
+                c as u32
+            );
+        }
+    }
+}
+
+/// Strict [`sanitize`]. Returns `s` unchanged if sanitization would not modify
+/// it, otherwise an [`Error::Disallowed`] for the first offending character.
+pub fn sanitize_strict(s: &str) -> Result<&str, Error> {
+    with_current(|policy| check(s, policy)).map(|_| s)
+}
+
+/// [`sanitize_strict`] for input of at most `max` bytes, such as a request
+/// body or prompt with a size limit. Longer input is an [`Error::TooLong`],
+/// without being scanned.
+///
+/// ```
+/// use langsan::{sanitize_strict_max, Error};
+///
+/// assert_eq!(sanitize_strict_max("hello", 5), Ok("hello"));
+/// assert_eq!(
+///     sanitize_strict_max("hello!", 5),
+///     Err(Error::TooLong { len: 6, max: 5 })
+/// );
+/// ```
+pub fn sanitize_strict_max(s: &str, max: usize) -> Result<&str, Error> {
+    if s.len() > max {
+        return Err(Error::TooLong { len: s.len(), max });
+    }
+    sanitize_strict(s)
+}
+
+/// Returns an [`Error::Disallowed`] for the first character of `s` that
+/// sanitizing with `policy` would remove or replace, if any.
+pub(crate) fn check<P>(s: &str, policy: &P) -> Result<(), Error>
+where
+    P: CharPolicy + ?Sized,
+{
+    match s
+        .char_indices()
+        .find(|&(i, c)| decide(s, i, c, policy) != Decision::Allow)
+    {
+        Some((offset, c)) => Err(Error::Disallowed { offset, c }),
+        None => Ok(()),
+    }
+}
+
+/// The maximal clean slices of `s`, in order, for writing sanitized output
+/// somewhere without building a [`String`]. Concatenated, they are what
+/// [`sanitize`] returns, except that characters the policy replaces split
+/// spans rather than being substituted, and there is no `verbose` marker.
+///
+/// ```
+/// use langsan::sanitize_spans;
+///
+/// let spans: Vec<_> = sanitize_spans("Hi\u{E0041} there\u{E0042}!").collect();
+/// assert_eq!(spans, ["Hi", "!"]);
+/// assert_eq!(sanitize_spans("clean").collect::<Vec<_>>(), ["clean"]);
+/// ```
+pub fn sanitize_spans(s: &str) -> impl Iterator<Item = &str> {
+    with_current(|policy| {
+        let report = scan(s, policy);
+        let parts = match report.as_ref().and_then(|r| r.removed.clone()) {
+            Some(span) => [&s[..span.start], &s[span.end..]],
+            None => [s, ""],
+        };
+        // The policy is only needed, and cloned, to find replacements.
+        let replacing = report.is_some_and(|r| r.replaced > 0);
+        Spans {
+            parts,
+            policy: replacing.then(|| policy.clone()),
+        }
+    })
+}
+
+/// Iterator for [`sanitize_spans`].
+struct Spans<'a> {
+    /// What's left before and after the removed span.
+    parts: [&'a str; 2],
+    /// The policy, if any characters are replaced.
+    policy: Option<Policy>,
+}
+
+impl<'a> Iterator for Spans<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            let part = self.parts.iter_mut().find(|p| !p.is_empty())?;
+            let replaced = self.policy.as_ref().and_then(|policy| {
+                part.char_indices()
+                    .find(|&(_, c)| matches!(classify_guarded(policy, c), Decision::Replace(_)))
+            });
+            match replaced {
+                Some((i, c)) => {
+                    let span = &part[..i];
+                    *part = &part[i + c.len_utf8()..];
+                    if !span.is_empty() {
+                        return Some(span);
+                    }
+                }
+                None => return Some(std::mem::take(part)),
+            }
+        }
+    }
+}
+
+/// [`sanitize`] raw bytes, such as data read straight from a socket or file.
+/// Invalid UTF-8 is first replaced with U+FFFD REPLACEMENT CHARACTER, which
+/// the active policy then decides on like any other character. Returns `bytes`
+/// unchanged if they are valid UTF-8 and sanitization would not modify them.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use langsan::sanitize_bytes;
+///
+/// assert!(matches!(sanitize_bytes(b"hello"), Cow::Borrowed(b"hello")));
+/// assert!(matches!(sanitize_bytes(b"hi \xF0\x9F\x8F\xB4"), Cow::Owned(_)));
+/// ```
+pub fn sanitize_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    match sanitize_lossy(bytes) {
+        Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+        Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+    }
+}
+
+/// [`sanitize_bytes`], always returning a [`String`].
+pub fn sanitize_bytes_lossy(bytes: &[u8]) -> String {
+    sanitize_lossy(bytes).into_owned()
+}
+
+/// Replace invalid UTF-8 in `bytes` and [`sanitize`] the result.
+fn sanitize_lossy(bytes: &[u8]) -> Cow<'_, str> {
+    match String::from_utf8_lossy(bytes) {
+        Cow::Borrowed(s) => sanitize(s).map_or(Cow::Borrowed(s), Cow::Owned),
+        Cow::Owned(s) => Cow::Owned(sanitize(&s).unwrap_or(s)),
+    }
+}
+
+/// Push `s` onto `out`, applying any [`Decision::Replace`]ments if `replaced`.
+/// `s` must not contain any removed characters.
+fn push_replaced<P>(out: &mut String, s: &str, policy: &P, replaced: bool)
+where
+    P: CharPolicy + ?Sized,
+{
+    if !replaced {
+        out.push_str(s);
+        return;
+    }
+    for c in s.chars() {
+        match classify_guarded(policy, c) {
+            Decision::Replace(r) => out.push(r),
+            _ => out.push(c),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Policy, Severity};
 
     #[test]
     fn test_sanitize() {
@@ -93,5 +498,282 @@ mod tests {
         assert_eq!(sanitize("👍"), None);
         #[cfg(feature = "emoji")]
         assert_eq!(sanitize("🙏"), None);
+        // Sequences are kept whole, but their joiners aren't allowed alone
+        #[cfg(feature = "emoji-full")]
+        assert_eq!(sanitize("👨‍👩‍👧 🏳️‍🌈 🧑🏽‍💻 1️⃣ ☀️ ⌚"), None);
+        #[cfg(all(feature = "emoji-full", not(feature = "verbose")))]
+        assert_eq!(sanitize("a\u{200D}b\u{FE0F}c").as_deref(), Some("ac"));
+        #[cfg(feature = "math")]
+        assert_eq!(sanitize("∀x ∈ ℝ: xⁿ ≥ 0 ⇒ ∑ 𝐱₁ ⊆ ⟨a, b⟩ → ∞ ⨁"), None);
+        // Only the safe parts of General Punctuation, not bidi controls
+        #[cfg(feature = "typographic-punct")]
+        assert_eq!(sanitize("“Quotes” — ‘and’ dashes… • ‹more›"), None);
+        #[cfg(all(feature = "typographic-punct", not(feature = "verbose")))]
+        assert_eq!(sanitize("“a\u{202E}b”").as_deref(), Some("“ab”"));
+        // Tables and TUI art in code output
+        #[cfg(feature = "box-drawing")]
+        assert_eq!(sanitize("┌──┬──┐\n│▀▄│░▒│\n╘══╧══╛ █▌"), None);
+        // Language presets enable everything their text needs
+        #[cfg(feature = "chinese")]
+        assert_eq!(sanitize("你好，世界。"), None);
+        #[cfg(feature = "japanese")]
+        assert_eq!(sanitize("こんにちは、カタカナと漢字！"), None);
+        #[cfg(feature = "korean")]
+        assert_eq!(sanitize("안녕하세요, 세계。"), None);
+        #[cfg(feature = "arabic")]
+        assert_eq!(sanitize("مرحبا ١٢٣ ﷺ ﻻ ݐ"), None);
+        // Maqaf, geresh, gershayim, a presentation form and the sheqel sign
+        #[cfg(feature = "hebrew")]
+        assert_eq!(sanitize("בֵּית־סֵפֶר ג׳ צה״ל ﬠ ₪5"), None);
+        // But not the rest of their blocks
+        #[cfg(all(feature = "hebrew", not(feature = "currency-symbols")))]
+        assert_eq!(sanitize("₪5 €5"), Some("₪5 5".to_owned()));
+        // Danda and double danda are in the Devanagari block
+        #[cfg(feature = "hindi")]
+        assert_eq!(sanitize("नमस्ते दुनिया। यह ठीक है॥ ०१२ क᳐ क꣠"), None);
+        #[cfg(feature = "thai")]
+        assert_eq!(sanitize("สวัสดีครับ ๑๒๓ ฯลฯ"), None);
+        // Precomposed and decomposed
+        #[cfg(feature = "vietnamese")]
+        assert_eq!(
+            sanitize("Xin chào thế giới! Đường phố ĩ ũ ơ ư ạ ỹ a\u{0301}"),
+            None
+        );
+        // Modern, polytonic and scientific
+        #[cfg(feature = "greek")]
+        assert_eq!(sanitize("Καλημέρα κόσμε; ἀρχὴ ᾧ, λ = h/p, Δx > 0"), None);
+        #[cfg(feature = "turkish")]
+        assert_eq!(
+            sanitize("Günaydın! İstanbul'da ağaçlar, şu ılık sabah."),
+            None
+        );
+        #[cfg(feature = "polish")]
+        assert_eq!(sanitize("Zażółć gęślą jaźń, Łódź."), None);
+        #[cfg(feature = "czech")]
+        assert_eq!(sanitize("Příliš žluťoučký kůň úpěl ďábelské ódy."), None);
+        #[cfg(feature = "hungarian")]
+        assert_eq!(sanitize("Árvíztűrő tükörfúrógép, Őrség."), None);
+        #[cfg(feature = "ukrainian")]
+        assert_eq!(sanitize("Привіт, світе! Ґанок, їжак, м'ята, Європа."), None);
+    }
+
+    #[test]
+    fn test_sanitize_outcome() {
+        let outcome = sanitize_outcome("hello");
+        assert_eq!(outcome, Outcome::Clean("hello"));
+        assert!(outcome.report().is_none());
+        assert!(matches!(outcome.into_cow(), Cow::Borrowed("hello")));
+
+        let outcome = sanitize_outcome("hi 🏴 there 🏴!");
+        assert!(!outcome.is_clean());
+        let report = outcome.report().unwrap();
+        assert_eq!(report.removed(), Some(3..18));
+        assert_eq!(report.removed_bytes(), 15);
+        assert_eq!(report.replaced(), 0);
+        assert_eq!(report.severity(), Severity::Medium);
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(outcome.as_str(), "hi !");
+        #[cfg(feature = "verbose")]
+        assert_eq!(outcome.as_str(), "hi [15 BYTES SANITIZED]!");
+
+        let policy = |c: char| match c {
+            '_' => Decision::Replace('-'),
+            'x' => Decision::Remove,
+            _ => Decision::Allow,
+        };
+        match sanitize_outcome_with("a_x_x_b_", &policy) {
+            Outcome::Modified { output, report } => {
+                #[cfg(not(feature = "verbose"))]
+                assert_eq!(output, "a--b-");
+                #[cfg(feature = "verbose")]
+                assert_eq!(output, "a-[3 BYTES SANITIZED]-b-");
+                assert_eq!(report.removed(), Some(2..5));
+                // the one between the `x`s is removed rather than replaced
+                assert_eq!(report.replaced(), 3);
+                assert!(!report.is_empty());
+            }
+            Outcome::Clean(_) => panic!("expected modification"),
+        }
+
+        // Hidden text is the most severe, and replacements the least
+        let report = sanitize_outcome("a\u{202E}b\u{E0041}").report().cloned();
+        assert_eq!(report.map(|r| r.severity()), Some(Severity::High));
+        let report = sanitize_outcome_with("a_", &policy).report().cloned();
+        assert_eq!(report.as_ref().map(Report::severity), Some(Severity::Info));
+        assert_eq!(report.map(|r| r.risk_score()), Some(0.0));
+    }
+
+    #[test]
+    fn test_sanitize_strict() {
+        assert_eq!(sanitize_strict("hello \t\n"), Ok("hello \t\n"));
+        assert_eq!(
+            sanitize_strict("hi 🏴"),
+            Err(Error::Disallowed {
+                offset: 3, c: '🏴'
+            })
+        );
+
+        assert_eq!(sanitize_strict_max("hi", 2), Ok("hi"));
+        assert_eq!(
+            sanitize_strict_max("hi 🏴", 3),
+            Err(Error::TooLong { len: 7, max: 3 })
+        );
+        assert_eq!(
+            sanitize_strict_max("hi 🏴", 7),
+            Err(Error::Disallowed {
+                offset: 3, c: '🏴'
+            })
+        );
+    }
+
+    #[test]
+    fn test_sanitize_both() {
+        let policy = Policy::new(&[0x20..=0x7E]);
+        let (quiet, marked) = sanitize_both_with("clean", &policy);
+        assert!(matches!(quiet, Cow::Borrowed("clean")));
+        assert_eq!(marked, "clean");
+        assert_eq!(
+            sanitize_both_with("a🏴b\u{202E}c", &policy),
+            (
+                Cow::Owned("ac".to_string()),
+                "a[8 BYTES SANITIZED]c".to_string()
+            )
+        );
+
+        // The policy's own marker, and replacements without removals
+        let policy = policy.with_marker(Marker::Text("[X]".into()));
+        assert_eq!(sanitize_both_with("🏴!", &policy).1, "[X]!");
+        let tabs = |c: char| match c {
+            '\t' => Decision::Replace(' '),
+            _ => Decision::Allow,
+        };
+        assert_eq!(
+            sanitize_both_with("a\tb", &tabs),
+            ("a b".into(), "a b".into())
+        );
+    }
+
+    #[test]
+    fn test_idempotent() {
+        let inputs = [
+            "hello",
+            "a🏴b",
+            "\u{E0041}hi\u{E0042}",
+            "x\u{202E}y\u{200D}z",
+            "👨\u{200D}👩\u{FE0F} 1\u{20E3}",
+            "a\tb\u{0}",
+            "",
+        ];
+        let tabs = |c: char| match c {
+            '\t' => Decision::Replace(' '),
+            'x' => Decision::Remove,
+            _ => Decision::Allow,
+        };
+        for input in inputs {
+            let policy = Policy::default().verbose(false);
+            let once = sanitize_with(input, &policy).unwrap_or_else(|| input.to_string());
+            assert_eq!(sanitize_with(&once, &policy), None, "{input:?}");
+            assert_eq!(check(&once, &policy), Ok(()));
+
+            let once = sanitize_with(input, &tabs).unwrap_or_else(|| input.to_string());
+            assert_eq!(sanitize_with(&once, &tabs), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "sanitization kept U+0078")]
+    fn test_debug_assert_kept() {
+        // A policy changing its mind is caught rather than keeping what it
+        // later removes.
+        let seen = std::cell::Cell::new(false);
+        let fickle = |c: char| match c {
+            'x' if seen.replace(true) => Decision::Remove,
+            'y' => Decision::Remove,
+            _ => Decision::Allow,
+        };
+        sanitize_with("xay", &fickle);
+    }
+
+    #[test]
+    fn test_sanitize_spans() {
+        let spans = |s| sanitize_spans(s).collect::<Vec<_>>();
+        assert!(spans("").is_empty());
+        assert_eq!(spans("hello \t\n"), ["hello \t\n"]);
+        assert_eq!(spans("a\u{E0041}b\u{E0042}c"), ["a", "c"]);
+        assert_eq!(spans("\u{E0041}ab"), ["ab"]);
+        assert_eq!(spans("ab\u{E0041}"), ["ab"]);
+
+        // Replaced characters split spans
+        let tabs = Policy::custom(|c: char| match c {
+            '\t' => Decision::Replace(' '),
+            _ if c.is_ascii() => Decision::Allow,
+            _ => Decision::Remove,
+        });
+        crate::with_policy(tabs, || {
+            assert_eq!(spans("a\tb\t\tc\u{E0041}d\te"), ["a", "b", "c", "d", "e"]);
+            assert_eq!(spans("\t"), Vec::<&str>::new());
+        });
+    }
+
+    #[test]
+    fn test_sanitize_bytes() {
+        assert!(matches!(sanitize_bytes(b"hello"), Cow::Borrowed(b"hello")));
+        assert_eq!(sanitize_bytes_lossy(b"hello"), "hello");
+
+        let ascii = Policy::new(&[0x20..=0x7E]);
+        crate::with_policy(ascii, || {
+            // Invalid UTF-8 is replaced, then removed like any other character
+            #[cfg(not(feature = "verbose"))]
+            {
+                assert_eq!(sanitize_bytes(b"a\xFFb").as_ref(), b"ab");
+                assert_eq!(sanitize_bytes_lossy(b"a\xF0\x9Fb\xC3\xA9c"), "ac");
+            }
+            #[cfg(feature = "verbose")]
+            assert_eq!(sanitize_bytes_lossy(b"a\xFFb"), "a[3 BYTES SANITIZED]b");
+        });
+        let replacement = Policy::new(&[0x20..=0x7E, 0xFFFD..=0xFFFD]);
+        crate::with_policy(replacement, || {
+            assert_eq!(sanitize_bytes_lossy(b"a\xFFb"), "a\u{FFFD}b");
+        });
+    }
+
+    #[test]
+    fn test_sanitize_with_char_policy() {
+        // Box drawing only, with tabs expanded to a space
+        let policy = |c: char| match c {
+            '\u{2500}'..='\u{257F}' => Decision::Allow,
+            '\t' => Decision::Replace(' '),
+            _ => Decision::Remove,
+        };
+        assert_eq!(sanitize_with("┌─┐", &policy), None);
+        assert_eq!(sanitize_with("┌\t┐", &policy).as_deref(), Some("┌ ┐"));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(sanitize_with("┌\tAB┐\t", &policy).as_deref(), Some("┌ ┐ "));
+        #[cfg(feature = "verbose")]
+        assert_eq!(
+            sanitize_with("┌\tAB┐\t", &policy).as_deref(),
+            Some("┌ [2 BYTES SANITIZED]┐ ")
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "thai", not(feature = "verbose")))]
+    fn test_sanitize_thai() {
+        // Thai has no spaces, so a span removed between zero width spaces,
+        // which some editors insert at word breaks, takes whole words with it
+        let text = "สวัสดี\u{200B}ครับ\u{200B}ผม";
+        assert_eq!(sanitize(text).as_deref(), Some("สวัสดีผม"));
+        // Handling characters one at a time keeps every word
+        let policy = crate::Policy::default();
+        let per_char = |c: char| match (policy.is_allowed(c), c) {
+            (true, _) => Decision::Allow,
+            (false, '\u{200B}') => Decision::Replace(' '),
+            (false, _) => Decision::Remove,
+        };
+        assert_eq!(
+            sanitize_with(text, &per_char).as_deref(),
+            Some("สวัสดี ครับ ผม")
+        );
     }
 }
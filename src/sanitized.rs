@@ -0,0 +1,153 @@
+/// A wrapper proving a value was sanitized.
+use std::{fmt, ops::Deref};
+
+use crate::{san::sanitize_strict, CowStr, Error, Sanitize};
+
+/// A value that has been through the sanitizer. It can only be constructed by
+/// sanitizing, so a signature like `fn build_prompt(user: Sanitized<&str>)`
+/// enforces sanitization at the type level. The value is read-only, so it
+/// can't be modified afterwards.
+///
+/// ```
+/// use langsan::{Sanitized, Sanitize};
+///
+/// fn build_prompt(user: Sanitized<&str>) -> String {
+///     format!("User: {user}")
+/// }
+///
+/// let input = Sanitized::new(String::from("hi🏴"));
+/// assert!(input.sanitize_strict().is_ok());
+/// build_prompt(input.as_deref());
+/// assert!(Sanitized::try_from("hi🏴").is_err());
+/// ```
+///
+/// Values are sanitized by the active policy when wrapped, and aren't checked
+/// again if a different policy becomes active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sanitized<T>(T);
+
+impl<T: Sanitize> Sanitized<T> {
+    /// Sanitize `value` in place and wrap it.
+    pub fn new(mut value: T) -> Self {
+        value.sanitize();
+        Self(value)
+    }
+
+    /// Strict version of [`Sanitized::new`] returning an [`Error`] rather than
+    /// modifying the value if it would be sanitized.
+    pub fn try_new(value: T) -> Result<Self, Error> {
+        value.sanitize_strict()?;
+        Ok(Self(value))
+    }
+}
+
+impl<T> Sanitized<T> {
+    /// Unwrap the sanitized value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Borrow the sanitized value.
+    pub fn as_ref(&self) -> Sanitized<&T> {
+        Sanitized(&self.0)
+    }
+
+    /// Borrow the sanitized value's target, such as a `&str` for a `String`.
+    pub fn as_deref(&self) -> Sanitized<&T::Target>
+    where
+        T: Deref,
+    {
+        Sanitized(&*self.0)
+    }
+}
+
+impl<T> Deref for Sanitized<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Sanitized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A borrowed string can't be sanitized in place, so it's checked strictly.
+impl<'a> TryFrom<&'a str> for Sanitized<&'a str> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self, Error> {
+        sanitize_strict(s).map(Self)
+    }
+}
+
+/// A [`CowStr`] has already been sanitized.
+impl<'a> From<CowStr<'a>> for Sanitized<CowStr<'a>> {
+    fn from(s: CowStr<'a>) -> Self {
+        Self(s)
+    }
+}
+
+impl<'a> From<&'a CowStr<'_>> for Sanitized<&'a str> {
+    fn from(s: &'a CowStr<'_>) -> Self {
+        Self(s.as_ref())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Sanitized<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Sanitizes the value once deserialized.
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Sanitized<T>
+where
+    T: serde::Deserialize<'de> + Sanitize,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(user: Sanitized<&str>) -> String {
+        format!("User: {user}")
+    }
+
+    #[test]
+    fn test_sanitized() {
+        let input = Sanitized::new(String::from("hi🏴"));
+        assert_eq!(input.sanitize_strict(), Ok(()));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(prompt(input.as_deref()), "User: hi");
+
+        let messages = Sanitized::new(vec![String::from("a🏴"), String::from("b")]);
+        assert_eq!(messages.len(), 2);
+        assert!(messages.sanitize_strict().is_ok());
+
+        assert_eq!(
+            Sanitized::try_new(String::from("hi🏴")),
+            Err(Error::Disallowed {
+                offset: 2, c: '🏴'
+            })
+        );
+        assert_eq!(
+            Sanitized::try_from("hi").map(prompt).as_deref(),
+            Ok("User: hi")
+        );
+        assert!(Sanitized::try_from("hi🏴").is_err());
+
+        let s = CowStr::from("hi🏴");
+        assert_eq!(prompt(Sanitized::from(&s)), format!("User: {s}"));
+        assert_eq!(Sanitized::from(s.clone()).into_inner(), s);
+    }
+}
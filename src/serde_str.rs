@@ -0,0 +1,64 @@
+//! Helpers to sanitize plain `String` fields with serde.
+//!
+//! ```
+//! #[derive(serde::Deserialize)]
+//! struct Prompt {
+//!     #[serde(with = "langsan::serde_str")]
+//!     content: String,
+//! }
+//!
+//! let prompt: Prompt = serde_json::from_str(r#"{"content":"Hi🏴"}"#).unwrap();
+//! # #[cfg(not(feature = "verbose"))]
+//! assert_eq!(prompt.content, "Hi");
+//! ```
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::sanitize;
+
+/// Serialize `s` [`sanitize`]d.
+pub fn serialize<S>(s: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match sanitize(s) {
+        Some(sanitized) => serializer.serialize_str(&sanitized),
+        None => serializer.serialize_str(s),
+    }
+}
+
+/// Deserialize a `String` and [`sanitize`] it.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(sanitize(&s).unwrap_or(s))
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[cfg(not(feature = "verbose"))]
+    struct Test {
+        #[serde(with = "crate::serde_str")]
+        s: String,
+        plain: String,
+    }
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_serde_str() {
+        let t: Test = serde_json::from_str(r#"{"s":"Hello🏴!","plain":"🏴"}"#).unwrap();
+        assert_eq!(t.s, "Hello!");
+        assert_eq!(t.plain, "🏴");
+
+        let t = Test {
+            s: "🏴Hello".into(),
+            plain: "🏴".into(),
+        };
+        assert_eq!(
+            serde_json::to_string(&t).unwrap(),
+            r#"{"s":"Hello","plain":"🏴"}"#
+        );
+    }
+}
@@ -0,0 +1,199 @@
+/// Database integrations. Strings are stored as text and sanitized as they are
+/// read back, so rows written before sanitization are cleaned on read.
+use crate::CowStr;
+
+#[cfg(feature = "sqlx")]
+impl<DB> sqlx::Type<DB> for CowStr<'_>
+where
+    DB: sqlx::Database,
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'q, DB> sqlx::Encode<'q, DB> for CowStr<'_>
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'q, DB>,
+{
+    fn encode(
+        self,
+        buf: &mut <DB as sqlx::Database>::ArgumentBuffer<'q>,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        self.into_owned().encode(buf)
+    }
+
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as sqlx::Database>::ArgumentBuffer<'q>,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        self.to_string().encode(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r, DB> sqlx::Decode<'r, DB> for CowStr<'_>
+where
+    DB: sqlx::Database,
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(
+        value: <DB as sqlx::Database>::ValueRef<'r>,
+    ) -> Result<Self, sqlx::error::BoxDynError> {
+        Ok(String::decode(value)?.into())
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for CowStr<'_>
+where
+    DB: diesel::backend::Backend,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(bytes)?.into())
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for CowStr<'_>
+where
+    DB: diesel::backend::Backend,
+    str: diesel::serialize::ToSql<diesel::sql_types::Text, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        self.as_ref().to_sql(out)
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+impl rusqlite::ToSql for CowStr<'_> {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.as_ref().into())
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+impl rusqlite::types::FromSql for CowStr<'_> {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value).map(CowStr::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sqlx")]
+    #[tokio::test]
+    async fn test_sqlx() {
+        use sqlx::{Connection, SqliteConnection};
+
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE messages (id INTEGER PRIMARY KEY, content TEXT NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO messages (id, content) VALUES (1, ?), (2, ?)")
+            .bind(CowStr::from("Hello, world!"))
+            .bind("legacy row🏴!")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let rows: Vec<(CowStr<'static>,)> =
+            sqlx::query_as("SELECT content FROM messages ORDER BY id")
+                .fetch_all(&mut conn)
+                .await
+                .unwrap();
+        assert_eq!(rows[0].0.as_ref(), "Hello, world!");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(rows[1].0.as_ref(), "legacy row!");
+        #[cfg(feature = "verbose")]
+        assert_eq!(rows[1].0.as_ref(), "legacy row[4 BYTES SANITIZED]!");
+    }
+
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn test_diesel() {
+        use crate::SanitizedString;
+        use diesel::{prelude::*, sql_types::Text, sqlite::SqliteConnection};
+
+        diesel::table! {
+            messages (id) {
+                id -> Integer,
+                content -> Text,
+            }
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query("CREATE TABLE messages (id INTEGER PRIMARY KEY, content TEXT NOT NULL)")
+            .execute(&mut conn)
+            .unwrap();
+        diesel::insert_into(messages::table)
+            .values((
+                messages::id.eq(1),
+                messages::content.eq(CowStr::from("Hello, world!")),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+        diesel::sql_query("INSERT INTO messages (id, content) VALUES (2, ?)")
+            .bind::<Text, _>("legacy row🏴!")
+            .execute(&mut conn)
+            .unwrap();
+
+        let rows: Vec<SanitizedString> = messages::table
+            .select(messages::content)
+            .order(messages::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(rows[0].as_ref(), "Hello, world!");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(rows[1].as_ref(), "legacy row!");
+        #[cfg(feature = "verbose")]
+        assert_eq!(rows[1].as_ref(), "legacy row[4 BYTES SANITIZED]!");
+    }
+
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn test_rusqlite() {
+        use crate::SanitizedString;
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE messages (id INTEGER PRIMARY KEY, content TEXT NOT NULL)")
+            .unwrap();
+        conn.execute(
+            "INSERT INTO messages (id, content) VALUES (1, ?1), (2, ?2)",
+            (CowStr::from("Hello, world!"), "legacy row🏴!"),
+        )
+        .unwrap();
+
+        let mut stmt = conn
+            .prepare("SELECT content FROM messages ORDER BY id")
+            .unwrap();
+        let rows: Vec<SanitizedString> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows[0].as_ref(), "Hello, world!");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(rows[1].as_ref(), "legacy row!");
+        #[cfg(feature = "verbose")]
+        assert_eq!(rows[1].as_ref(), "legacy row[4 BYTES SANITIZED]!");
+    }
+}
@@ -0,0 +1,263 @@
+/// Sanitization of server-sent event streams.
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use serde_json::Value;
+
+use crate::{sanitize, sanitize_json};
+
+/// Incrementally sanitizes a server-sent events byte stream, such as a
+/// streamed chat completion, with the active policy. Complete lines are
+/// sanitized and re-emitted as they arrive, so the stream is never fully
+/// buffered.
+///
+/// `data:` fields holding JSON have every string sanitized, including object
+/// keys, so content deltas remain valid JSON. Everything else, including
+/// `data: [DONE]`, is sanitized as text. Each line is sanitized on its own, so
+/// a removed span never extends across lines or events.
+///
+/// Incomplete lines are buffered up to a [maximum length], 1 MiB by default,
+/// so a stream without newlines can't grow the buffer without bound.
+///
+/// [maximum length]: SseSanitizer::max_line_len
+#[derive(Debug)]
+pub struct SseSanitizer {
+    buf: Vec<u8>,
+    max_line_len: usize,
+}
+
+/// The default [`SseSanitizer::max_line_len`].
+const DEFAULT_MAX_LINE_LEN: usize = 1024 * 1024;
+
+impl Default for SseSanitizer {
+    fn default() -> Self {
+        Self {
+            buf: Vec::new(),
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+        }
+    }
+}
+
+impl SseSanitizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most bytes of an incomplete line to buffer. Defaults to 1 MiB.
+    /// Beyond that, what's buffered is sanitized as text and returned, and
+    /// the rest of the line is handled as if it were a new one, so a `data:`
+    /// field that long won't be sanitized as JSON.
+    pub fn max_line_len(mut self, bytes: usize) -> Self {
+        self.max_line_len = bytes;
+        self
+    }
+
+    /// Feed the next `chunk` of the stream, returning the sanitized complete
+    /// lines. A trailing incomplete line is held until a later chunk completes
+    /// it, it exceeds the [`SseSanitizer::max_line_len`], or
+    /// [`SseSanitizer::finish`] is called.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<u8> {
+        self.buf.extend_from_slice(chunk);
+        let end = self
+            .buf
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let mut out = Vec::with_capacity(end);
+        for line in self.buf[..end].split_inclusive(|&b| b == b'\n') {
+            sanitize_line(line, &mut out);
+        }
+        self.buf.drain(..end);
+        if self.buf.len() > self.max_line_len {
+            // Keep a character split by the chunk for the next one
+            let end = match std::str::from_utf8(&self.buf) {
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                _ => self.buf.len(),
+            };
+            sanitize_line(&self.buf[..end], &mut out);
+            self.buf.drain(..end);
+        }
+        out
+    }
+
+    /// Sanitize and return any incomplete line left at the end of the stream.
+    pub fn finish(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        if !self.buf.is_empty() {
+            sanitize_line(&self.buf, &mut out);
+            self.buf.clear();
+        }
+        out
+    }
+}
+
+/// Sanitize a single `line`, including any line ending, onto `out`.
+fn sanitize_line(line: &[u8], out: &mut Vec<u8>) {
+    let content = line.strip_suffix(b"\n").unwrap_or(line);
+    let content = content.strip_suffix(b"\r").unwrap_or(content);
+    let ending = &line[content.len()..];
+    let text = String::from_utf8_lossy(content);
+
+    if let Some(data) = text.strip_prefix("data:") {
+        if let Ok(mut value) = serde_json::from_str::<Value>(data) {
+            if value.is_object() || value.is_array() {
+                match sanitize_json(&mut value, true) {
+                    0 => out.extend_from_slice(text.as_bytes()),
+                    _ => {
+                        let space = if data.starts_with(' ') { " " } else { "" };
+                        out.extend_from_slice(format!("data:{}{}", space, value).as_bytes());
+                    }
+                }
+                out.extend_from_slice(ending);
+                return;
+            }
+        }
+    }
+
+    match sanitize(&text) {
+        Some(sanitized) => out.extend_from_slice(sanitized.as_bytes()),
+        None => out.extend_from_slice(text.as_bytes()),
+    }
+    out.extend_from_slice(ending);
+}
+
+/// A [`Stream`] adapter sanitizing a server-sent events byte stream with an
+/// [`SseSanitizer`]. Created by [`sanitize_sse`].
+#[derive(Debug)]
+pub struct SseStream<S> {
+    inner: S,
+    sanitizer: SseSanitizer,
+    done: bool,
+}
+
+impl<S> SseStream<S> {
+    /// The most bytes of an incomplete line to buffer, as for
+    /// [`SseSanitizer::max_line_len`]. Defaults to 1 MiB.
+    pub fn max_line_len(mut self, bytes: usize) -> Self {
+        self.sanitizer.max_line_len = bytes;
+        self
+    }
+}
+
+/// Sanitize a server-sent events byte `stream`, such as the body of a streamed
+/// completion, without buffering it. Errors are passed through as-is. Box and
+/// pin the stream if it isn't [`Unpin`].
+pub fn sanitize_sse<S>(stream: S) -> SseStream<S> {
+    SseStream {
+        inner: stream,
+        sanitizer: SseSanitizer::new(),
+        done: false,
+    }
+}
+
+impl<S, B, E> Stream for SseStream<S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    type Item = Result<Bytes, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        while !self.done {
+            let out = match ready!(Pin::new(&mut self.inner).poll_next(cx)) {
+                Some(Ok(chunk)) => self.sanitizer.push(chunk.as_ref()),
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    self.done = true;
+                    self.sanitizer.finish()
+                }
+            };
+            if !out.is_empty() {
+                return Poll::Ready(Some(Ok(out.into())));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures::{executor::block_on, stream, StreamExt};
+
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_sse_sanitizer() {
+        let mut sanitizer = SseSanitizer::new();
+        // Chunks may split lines and even characters
+        let event = "data: {\"delta\":{\"content\":\"hi🏴\"}}\r\n\r\n".as_bytes();
+        let (a, b) = event.split_at(30);
+        assert_eq!(sanitizer.push(a), b"");
+        assert_eq!(
+            String::from_utf8(sanitizer.push(b)).unwrap(),
+            "data: {\"delta\":{\"content\":\"hi\"}}\r\n\r\n"
+        );
+
+        // Clean JSON is passed through as-is
+        let event = b"event: delta\ndata:{ \"a\": 1 }\n\n";
+        assert_eq!(sanitizer.push(event), event);
+
+        // Anything else is sanitized as text
+        assert_eq!(sanitizer.push(b": ping\xF0\x9F\x8F\xB4\n"), b": ping\n");
+        assert_eq!(sanitizer.push(b"data: [DONE]"), b"");
+        assert_eq!(sanitizer.finish(), b"data: [DONE]");
+        assert_eq!(sanitizer.finish(), b"");
+    }
+
+    #[test]
+    #[cfg(not(feature = "verbose"))]
+    fn test_max_line_len() {
+        let mut sanitizer = SseSanitizer::new().max_line_len(8);
+        assert_eq!(sanitizer.push(b"data: hi"), b"");
+        // Without a newline, a long line is flushed rather than buffered
+        assert_eq!(sanitizer.push(b"\xF0\x9F\x8F\xB4 there"), b"data: hi there");
+        assert!(sanitizer.buf.is_empty());
+        // Except for a character split by the chunk
+        assert_eq!(sanitizer.push(b"123456789\xC3"), b"123456789");
+        assert_eq!(sanitizer.push(b"\xA9\n"), b"\n");
+        let mut sanitizer = SseSanitizer::new().max_line_len(4);
+        for _ in 0..100 {
+            sanitizer.push(b"abcdef");
+            assert!(sanitizer.buf.len() <= 4);
+        }
+
+        let chunks = ["data: ", "0123456789", "\n"];
+        let stream = stream::iter(chunks.map(Ok::<_, Infallible>));
+        let out: Vec<Bytes> = block_on(
+            sanitize_sse(stream)
+                .max_line_len(8)
+                .map(Result::unwrap)
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(out, ["data: 0123456789", "\n"]);
+    }
+
+    #[test]
+    fn test_sanitize_sse() {
+        let chunks = [
+            "data: {\"content\":\"a",
+            "🏴\"}\n\ndata: {\"content\":\"b\"}\n\n",
+            "data: [DONE]\n\n",
+        ];
+        let stream = stream::iter(chunks.map(Ok::<_, Infallible>));
+        let out: Vec<Bytes> =
+            block_on(sanitize_sse(stream).map(Result::unwrap).collect::<Vec<_>>());
+        assert_eq!(out.len(), 2);
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(
+            out.concat(),
+            b"data: {\"content\":\"a\"}\n\ndata: {\"content\":\"b\"}\n\ndata: [DONE]\n\n"
+        );
+
+        let stream = stream::iter([Ok("data: x\n"), Err("oops")]);
+        let out: Vec<_> = block_on(sanitize_sse(stream).collect());
+        assert_eq!(out, [Ok(Bytes::from("data: x\n")), Err("oops")]);
+    }
+}
@@ -0,0 +1,176 @@
+/// Removal statistics aggregated over many inputs.
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{policy::with_current, sanitize_outcome_with, CharPolicy, Outcome, Report};
+
+/// What sanitization removed over many inputs, for logging which Unicode
+/// blocks are actually used against a service. Recording a [`Report`] is only
+/// a few additions, since sanitization computes it anyway.
+///
+/// ```
+/// use langsan::Stats;
+///
+/// let mut stats = Stats::new();
+/// stats.sanitize("hi\u{E0041}\u{E0042}");
+/// stats.sanitize("hello");
+/// assert_eq!((stats.inputs(), stats.modified()), (2, 1));
+/// assert_eq!(stats.removed_by_block()["Tags"], 2);
+/// assert_eq!(stats.max_run(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    inputs: usize,
+    modified: usize,
+    removed_bytes: usize,
+    replaced: usize,
+    max_run: usize,
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "crate::report::deserialize_blocks")
+    )]
+    removed_by_block: BTreeMap<&'static str, usize>,
+}
+
+impl Stats {
+    /// Empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sanitize `s` with the active policy, recording what was changed.
+    pub fn sanitize<'a>(&mut self, s: &'a str) -> Cow<'a, str> {
+        with_current(|policy| self.sanitize_with(s, policy))
+    }
+
+    /// [`sanitize`] with an explicit `policy` rather than the active one.
+    ///
+    /// [`sanitize`]: Stats::sanitize
+    pub fn sanitize_with<'a, P>(&mut self, s: &'a str, policy: &P) -> Cow<'a, str>
+    where
+        P: CharPolicy + ?Sized,
+    {
+        let outcome = sanitize_outcome_with(s, policy);
+        self.record(outcome.report());
+        outcome.into_cow()
+    }
+
+    /// Record an input, with the [`Report`] of what sanitizing it changed, or
+    /// `None` if it was clean, as from [`Outcome::report`].
+    pub fn record(&mut self, report: Option<&Report>) {
+        self.inputs += 1;
+        let Some(report) = report else {
+            return;
+        };
+        self.modified += 1;
+        self.removed_bytes += report.removed_bytes();
+        self.replaced += report.replaced();
+        self.max_run = self.max_run.max(report.max_run());
+        for (block, count) in report.removed_by_block() {
+            *self.removed_by_block.entry(*block).or_default() += count;
+        }
+    }
+
+    /// Add `other`'s statistics to these, such as to combine those of several
+    /// threads.
+    pub fn merge(&mut self, other: &Stats) {
+        self.inputs += other.inputs;
+        self.modified += other.modified;
+        self.removed_bytes += other.removed_bytes;
+        self.replaced += other.replaced;
+        self.max_run = self.max_run.max(other.max_run);
+        for (block, count) in &other.removed_by_block {
+            *self.removed_by_block.entry(*block).or_default() += count;
+        }
+    }
+
+    /// The number of inputs recorded.
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// The number of inputs sanitization modified.
+    pub fn modified(&self) -> usize {
+        self.modified
+    }
+
+    /// The total number of bytes removed.
+    pub fn removed_bytes(&self) -> usize {
+        self.removed_bytes
+    }
+
+    /// The total number of characters replaced.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// The longest run of consecutive removed characters in any input.
+    pub fn max_run(&self) -> usize {
+        self.max_run
+    }
+
+    /// The total number of characters removed from each Unicode block.
+    pub fn removed_by_block(&self) -> &BTreeMap<&'static str, usize> {
+        &self.removed_by_block
+    }
+}
+
+impl<'a, 'b> Extend<&'b Outcome<'a>> for Stats {
+    fn extend<I: IntoIterator<Item = &'b Outcome<'a>>>(&mut self, outcomes: I) {
+        for outcome in outcomes {
+            self.record(outcome.report());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Decision, Policy};
+
+    #[test]
+    fn test_stats() {
+        let policy = Policy::new(&[0x20..=0x7E]);
+        let mut stats = Stats::new();
+        assert_eq!(stats.sanitize_with("clean", &policy), "clean");
+        stats.sanitize_with("a🏴🏴b\u{E0041}", &policy);
+        stats.sanitize_with("\u{E0041}\u{E0042}\u{E0043}", &policy);
+        assert_eq!((stats.inputs(), stats.modified()), (3, 2));
+        assert_eq!(stats.removed_by_block()["Tags"], 4);
+        assert_eq!(
+            stats.removed_by_block()["Miscellaneous Symbols and Pictographs"],
+            2
+        );
+        assert_eq!(stats.max_run(), 3);
+        assert_eq!(stats.removed_by_block().values().sum::<usize>(), 6);
+
+        let tabs = |c: char| match c {
+            '\t' => Decision::Replace(' '),
+            _ => Decision::Allow,
+        };
+        let mut other = Stats::new();
+        other.sanitize_with("a\tb\t", &tabs);
+        assert_eq!((other.replaced(), other.max_run()), (2, 0));
+        stats.merge(&other);
+        assert_eq!((stats.inputs(), stats.replaced()), (4, 2));
+
+        let outcomes = [
+            sanitize_outcome_with("🏴", &policy),
+            sanitize_outcome_with("ok", &policy),
+        ];
+        let mut extended = Stats::new();
+        extended.extend(&outcomes);
+        assert_eq!(extended.removed_bytes(), 4);
+        assert_eq!(extended.inputs(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let mut stats = Stats::new();
+        stats.sanitize("a\u{E0041}");
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["removed_by_block"]["Tags"], 1);
+        assert_eq!(serde_json::from_value::<Stats>(json).unwrap(), stats);
+    }
+}
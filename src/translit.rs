@@ -0,0 +1,118 @@
+/// Transliteration of disallowed letters to ASCII.
+use std::borrow::Cow;
+
+use deunicode::deunicode_char;
+
+use crate::{
+    policy::{classify_guarded, with_current},
+    sanitize_outcome_with, CharPolicy, Decision,
+};
+
+/// Sanitize `s` with the active policy, but first transliterate letters and
+/// digits it would remove to ASCII approximations, if the policy allows them,
+/// so names and places keep their meaning rather than disappearing. Anything
+/// else is sanitized as usual, so the result is always clean.
+///
+/// Note this also turns homoglyph spoofs into the text they imitate. Use
+/// [`analyze`] first to detect those.
+///
+/// ```
+/// use langsan::{transliterate_with, Policy};
+///
+/// let ascii = Policy::new(&[0x20..=0x7E]).verbose(false);
+/// assert_eq!(transliterate_with("Zoë in Москва", &ascii), "Zoe in Moskva");
+/// assert_eq!(transliterate_with("北京", &ascii), "Bei Jing");
+/// // Invisible characters are still removed
+/// assert_eq!(transliterate_with("Åsa\u{202E}!", &ascii), "Asa!");
+/// ```
+///
+/// [`analyze`]: crate::analyze
+pub fn transliterate(s: &str) -> Cow<'_, str> {
+    with_current(|policy| transliterate_with(s, policy))
+}
+
+/// [`transliterate`] with an explicit `policy` rather than the active one.
+pub fn transliterate_with<'a, P>(s: &'a str, policy: &P) -> Cow<'a, str>
+where
+    P: CharPolicy + ?Sized,
+{
+    let mut out = String::new();
+    let mut last = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let Some(ascii) = approximate(c, policy) else {
+            continue;
+        };
+        out.push_str(&s[last..i]);
+        // Ideographs are transliterated a word at a time, each followed by a
+        // space, which isn't needed before punctuation or the end.
+        match chars.peek() {
+            Some((_, next)) if next.is_alphanumeric() => out.push_str(ascii),
+            _ => out.push_str(ascii.trim_end()),
+        }
+        last = i + c.len_utf8();
+    }
+    if last == 0 {
+        return sanitize_outcome_with(s, policy).into_cow();
+    }
+    out.push_str(&s[last..]);
+    match sanitize_outcome_with(&out, policy).into_cow() {
+        Cow::Borrowed(_) => Cow::Owned(out),
+        Cow::Owned(sanitized) => Cow::Owned(sanitized),
+    }
+}
+
+/// The ASCII approximation of `c`, if it's a letter or digit `policy` would
+/// remove, and it has one `policy` allows.
+fn approximate<P>(c: char, policy: &P) -> Option<&'static str>
+where
+    P: CharPolicy + ?Sized,
+{
+    if !c.is_alphanumeric() || classify_guarded(policy, c) != Decision::Remove {
+        return None;
+    }
+    deunicode_char(c).filter(|ascii| {
+        !ascii.trim().is_empty()
+            && ascii
+                .chars()
+                .all(|a| classify_guarded(policy, a) == Decision::Allow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+
+    #[test]
+    fn test_transliterate() {
+        let ascii = Policy::new(&[0x20..=0x7E]).verbose(false);
+        assert!(matches!(
+            transliterate_with("plain", &ascii),
+            Cow::Borrowed("plain")
+        ));
+        assert_eq!(transliterate_with("Straße", &ascii), "Strasse");
+        assert_eq!(
+            transliterate_with("Ελλάδα, Αθήνα", &ascii),
+            "Ellada, Athena"
+        );
+        assert_eq!(transliterate_with("東京タワー", &ascii), "Dong Jing tawa");
+        // Homoglyphs become what they imitate
+        assert_eq!(transliterate_with("p\u{430}ypal", &ascii), "paypal");
+        // Symbols and invisible characters are removed as usual
+        assert_eq!(transliterate_with("a→b", &ascii), "ab");
+        assert_eq!(transliterate_with("Ж\u{E0041}", &ascii), "Zh");
+
+        // Only into what the policy allows
+        let lower = Policy::new(&[0x61..=0x7A]).verbose(false);
+        assert_eq!(transliterate_with("éa", &lower), "ea");
+        assert_eq!(transliterate_with("Éa", &lower), "a");
+        // Replacements win
+        let policy = |c: char| match c {
+            'é' => Decision::Replace('e'),
+            c if c.is_ascii() => Decision::Allow,
+            _ => Decision::Remove,
+        };
+        assert_eq!(transliterate_with("éß", &policy), "ess");
+    }
+}
@@ -0,0 +1,87 @@
+/// [`validator`] checks that strings are clean.
+use std::borrow::Cow;
+
+use validator::ValidationError;
+
+use crate::{sanitize_strict, Error};
+
+/// Check that sanitizing `value` with the active policy wouldn't modify it,
+/// for the [`validator`] derive, so "must be clean" can be declared with the
+/// rest of a struct's validation. With validator 0.16 and earlier, use
+/// `#[validate(custom = "langsan::validate_sanitized")]` instead.
+///
+/// The error's code is `"sanitized"`, and its `offset` and `char` params are
+/// the byte offset and code point of the first character sanitization would
+/// change.
+///
+/// ```
+/// use validator::Validate;
+///
+/// #[derive(Validate)]
+/// struct Prompt {
+///     #[validate(custom(function = "langsan::validate_sanitized"))]
+///     text: String,
+/// }
+///
+/// assert!(Prompt { text: "Hi!".into() }.validate().is_ok());
+/// let errors = Prompt { text: "Hi🏴!".into() }.validate().unwrap_err();
+/// assert_eq!(errors.field_errors()["text"][0].code, "sanitized");
+/// ```
+pub fn validate_sanitized<T>(value: &T) -> Result<(), ValidationError>
+where
+    T: AsRef<str> + ?Sized,
+{
+    match sanitize_strict(value.as_ref()) {
+        Ok(_) => Ok(()),
+        Err(e @ Error::Disallowed { offset, c }) => {
+            let mut error =
+                ValidationError::new("sanitized").with_message(Cow::Owned(e.to_string()));
+            error.add_param(Cow::Borrowed("offset"), &offset);
+            error.add_param(Cow::Borrowed("char"), &format!("U+{:04X}", c as u32));
+            Err(error)
+        }
+        Err(e) => Err(ValidationError::new("sanitized").with_message(Cow::Owned(e.to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{with_policy, Policy};
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Message {
+        #[validate(custom(function = "validate_sanitized"))]
+        content: String,
+        #[validate(custom(function = "validate_sanitized"))]
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_validate_sanitized() {
+        assert_eq!(validate_sanitized("hello"), Ok(()));
+        let error = validate_sanitized("hi🏴").unwrap_err();
+        assert_eq!(error.code, "sanitized");
+        assert_eq!(error.params["offset"], 2);
+        assert_eq!(error.params["char"], "U+1F3F4");
+        assert_eq!(
+            error.message.as_deref(),
+            Some("disallowed character U+1F3F4 at byte 2")
+        );
+
+        let message = Message {
+            content: "hello".into(),
+            name: Some("bob🏴".into()),
+        };
+        let errors = message.validate().unwrap_err();
+        assert!(!errors.field_errors().contains_key("content"));
+        assert_eq!(errors.field_errors()["name"][0].code, "sanitized");
+
+        // With the active policy
+        with_policy(Policy::new(&[0x61..=0x7A]), || {
+            assert!(validate_sanitized(&String::from("hello")).is_ok());
+            assert!(validate_sanitized("Hello").is_err());
+        });
+    }
+}
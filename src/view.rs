@@ -0,0 +1,113 @@
+/// A lazily sanitized view of a string.
+use std::{borrow::Cow, cell::OnceCell, fmt};
+
+use crate::sanitize;
+
+/// A `&str` that is only sanitized when it's read, with [`as_str`] or
+/// [`Display`], for code that often builds strings it rarely uses. The result
+/// is cached, so the input is scanned at most once.
+///
+/// The active policy at the first read is used, not the one when the view was
+/// created.
+///
+/// ```
+/// use langsan::SanitizedView;
+///
+/// let view = SanitizedView::new("hi🏴");
+/// assert!(!view.is_evaluated());
+/// assert_eq!(view.raw(), "hi🏴");
+/// let prompt = format!("User: {view}");
+/// assert!(view.is_evaluated());
+/// # #[cfg(not(feature = "verbose"))]
+/// assert_eq!(prompt, "User: hi");
+/// ```
+///
+/// [`as_str`]: SanitizedView::as_str
+/// [`Display`]: fmt::Display
+#[derive(Debug, Clone)]
+pub struct SanitizedView<'a> {
+    raw: &'a str,
+    /// The sanitized copy, or `None` if the input is clean.
+    sanitized: OnceCell<Option<String>>,
+}
+
+impl<'a> SanitizedView<'a> {
+    /// Wrap `raw` without sanitizing it yet.
+    pub fn new(raw: &'a str) -> Self {
+        Self {
+            raw,
+            sanitized: OnceCell::new(),
+        }
+    }
+
+    /// The input, unsanitized.
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    /// The sanitized string, sanitizing it if this is the first read.
+    pub fn as_str(&self) -> &str {
+        self.evaluate().as_deref().unwrap_or(self.raw)
+    }
+
+    /// Returns true if sanitization modified the input, sanitizing it if this
+    /// is the first read.
+    pub fn is_modified(&self) -> bool {
+        self.evaluate().is_some()
+    }
+
+    /// Returns true if the input has been sanitized already.
+    pub fn is_evaluated(&self) -> bool {
+        self.sanitized.get().is_some()
+    }
+
+    /// The sanitized string, borrowed if sanitization didn't modify it.
+    pub fn into_cow(self) -> Cow<'a, str> {
+        self.evaluate();
+        match self.sanitized.into_inner().flatten() {
+            Some(sanitized) => Cow::Owned(sanitized),
+            None => Cow::Borrowed(self.raw),
+        }
+    }
+
+    fn evaluate(&self) -> &Option<String> {
+        self.sanitized.get_or_init(|| sanitize(self.raw))
+    }
+}
+
+impl<'a> From<&'a str> for SanitizedView<'a> {
+    fn from(raw: &'a str) -> Self {
+        Self::new(raw)
+    }
+}
+
+impl fmt::Display for SanitizedView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitized_view() {
+        let view = SanitizedView::from("hello");
+        assert!(!view.is_evaluated());
+        assert!(!view.is_modified());
+        assert!(view.is_evaluated());
+        assert!(matches!(view.into_cow(), Cow::Borrowed("hello")));
+
+        let view = SanitizedView::new("a🏴b");
+        assert!(matches!(view.clone().into_cow(), Cow::Owned(_)));
+        assert!(!view.is_evaluated());
+        assert!(view.is_modified());
+        #[cfg(not(feature = "verbose"))]
+        {
+            assert_eq!(view.as_str(), "ab");
+            assert_eq!(view.to_string(), "ab");
+        }
+        assert_eq!(view.raw(), "a🏴b");
+    }
+}
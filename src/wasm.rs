@@ -0,0 +1,140 @@
+//! [`wasm_bindgen`] bindings so JavaScript frontends sanitize with the same
+//! rules as the Rust backend.
+//!
+//! The crate is only built as an `rlib`, so `wasm-pack build` won't work
+//! as-is. Override the crate type with `cargo rustc`, then generate the
+//! JavaScript glue with the `wasm-bindgen` CLI, whose version must match the
+//! `wasm-bindgen` crate's:
+//!
+//! ```text
+//! cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/langsan.wasm
+//! ```
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{sanitize, sanitize_strict, sanitize_with, with_policy, Error, Policy};
+
+/// A [`Policy`] as accepted from JavaScript. All fields are optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PolicySpec {
+    /// Allowed inclusive code point ranges, the enabled ranges if omitted.
+    ranges: Option<Vec<(u32, u32)>>,
+    /// Characters to forbid even if they are in `ranges`.
+    forbid: String,
+}
+
+impl TryFrom<PolicySpec> for Policy {
+    type Error = Error;
+
+    fn try_from(spec: PolicySpec) -> Result<Self, Error> {
+        let policy = match spec.ranges {
+            Some(ranges) => {
+                if let Some((start, end)) = ranges
+                    .iter()
+                    .find(|(start, end)| start > end || *end > char::MAX as u32)
+                {
+                    return Err(Error::InvalidPolicy(format!(
+                        "invalid range [{}, {}]",
+                        start, end
+                    )));
+                }
+                let ranges: Vec<_> = ranges.into_iter().map(|(start, end)| start..=end).collect();
+                Policy::new(ranges)
+            }
+            None => Policy::default(),
+        };
+        Ok(policy.forbid(spec.forbid.chars()))
+    }
+}
+
+/// A sanitization policy, exported to JavaScript as `Policy`:
+///
+/// ```js
+/// const policy = new Policy({ ranges: [[0x20, 0x7e]], forbid: "<>" });
+/// policy.sanitize("hello <world>");
+/// ```
+#[wasm_bindgen(js_name = Policy)]
+pub struct WasmPolicy(Policy);
+
+#[wasm_bindgen(js_class = Policy)]
+impl WasmPolicy {
+    /// Create a policy from an object with optional `ranges` (an array of
+    /// inclusive `[start, end]` code points) and `forbid` (a string of
+    /// characters). With no argument, this is the default policy.
+    #[wasm_bindgen(constructor)]
+    pub fn new(spec: JsValue) -> Result<WasmPolicy, JsError> {
+        let spec = match spec.is_undefined() {
+            true => PolicySpec::default(),
+            false => serde_wasm_bindgen::from_value(spec)
+                .map_err(|e| JsError::new(&format!("invalid policy: {}", e)))?,
+        };
+        Ok(WasmPolicy(Policy::try_from(spec)?))
+    }
+
+    /// Sanitize `input` with this policy.
+    pub fn sanitize(&self, input: &str) -> String {
+        sanitize_with(input, &self.0).unwrap_or_else(|| input.to_string())
+    }
+
+    /// Return `input` unchanged, or throw if sanitization would modify it.
+    #[wasm_bindgen(js_name = sanitizeStrict)]
+    pub fn sanitize_strict(&self, input: &str) -> Result<String, JsError> {
+        with_policy(self.0.clone(), || sanitize_strict_js(input))
+    }
+
+    /// Returns true if `c` survives sanitization unchanged.
+    #[wasm_bindgen(js_name = isAllowed)]
+    pub fn is_allowed(&self, c: char) -> bool {
+        self.0.is_allowed(c)
+    }
+}
+
+/// Sanitize `input` with the default policy.
+#[wasm_bindgen(js_name = sanitize)]
+pub fn sanitize_js(input: &str) -> String {
+    sanitize(input).unwrap_or_else(|| input.to_string())
+}
+
+/// Return `input` unchanged, or throw if sanitization with the default policy
+/// would modify it.
+#[wasm_bindgen(js_name = sanitizeStrict)]
+pub fn sanitize_strict_js(input: &str) -> Result<String, JsError> {
+    Ok(sanitize_strict(input)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(json: &str) -> Result<WasmPolicy, Error> {
+        let spec: PolicySpec = serde_json::from_str(json).unwrap();
+        Policy::try_from(spec).map(WasmPolicy)
+    }
+
+    #[test]
+    fn test_policy_spec() {
+        let policy_ = policy(r#"{"ranges": [[32, 126]], "forbid": "<>"}"#).unwrap();
+        assert!(policy_.is_allowed('a'));
+        assert!(!policy_.is_allowed('<'));
+        assert!(!policy_.is_allowed('\n'));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(policy_.sanitize("hi <there>!"), "hi !");
+        assert_eq!(policy_.sanitize("hi"), "hi");
+
+        let default = policy("{}").unwrap();
+        assert_eq!(default.0, Policy::default());
+        assert!(default.is_allowed('\n'));
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(sanitize_js("hi🏴"), "hi");
+        assert_eq!(sanitize_strict_js("hi").unwrap(), "hi");
+
+        assert_eq!(
+            policy(r#"{"ranges": [[126, 32]]}"#).err(),
+            Some(Error::InvalidPolicy("invalid range [126, 32]".into()))
+        );
+        assert!(policy(r#"{"ranges": [[0, 1114112]]}"#).is_err());
+        assert!(serde_json::from_str::<PolicySpec>(r#"{"allow": []}"#).is_err());
+    }
+}
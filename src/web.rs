@@ -0,0 +1,367 @@
+/// Web framework extractors and responders for sanitized payloads.
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{sanitize_json, sanitize_json_strict, Error};
+
+/// A JSON request body with every string, including object keys, sanitized
+/// with the active policy before it is deserialized into `T`. Handlers never
+/// see unsanitized text.
+///
+/// A body that isn't valid JSON is rejected with `400 Bad Request` and one
+/// that doesn't match `T` with `422 Unprocessable Entity`. With the `actix`
+/// feature it can also be returned from a handler to sanitize the response.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SanitizedJson<T>(pub T);
+
+/// Like [`SanitizedJson`] but the request is rejected with `422 Unprocessable
+/// Entity` if any string would be modified by sanitization.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StrictJson<T>(pub T);
+
+/// Why a payload was rejected.
+#[derive(Debug)]
+enum Rejection {
+    /// The body is not valid JSON.
+    Syntax(serde_json::Error),
+    /// The body is valid JSON but doesn't match the target type.
+    Data(serde_json::Error),
+    /// Strict sanitization failed.
+    Disallowed(Error),
+}
+
+impl Rejection {
+    /// True if this is a client syntax error rather than a semantic one.
+    fn is_syntax(&self) -> bool {
+        matches!(self, Rejection::Syntax(_))
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Syntax(e) => write!(f, "invalid JSON: {}", e),
+            Rejection::Data(e) => write!(f, "invalid payload: {}", e),
+            Rejection::Disallowed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Parse `body` as JSON, sanitize it (or reject it if `strict`), and
+/// deserialize it into `T`.
+fn from_body<T>(body: &[u8], strict: bool) -> Result<T, Rejection>
+where
+    T: DeserializeOwned,
+{
+    let mut value: Value = serde_json::from_slice(body).map_err(Rejection::Syntax)?;
+    if strict {
+        sanitize_json_strict(&value, true).map_err(Rejection::Disallowed)?;
+    } else {
+        sanitize_json(&mut value, true);
+    }
+    serde_json::from_value(value).map_err(Rejection::Data)
+}
+
+#[cfg(feature = "axum")]
+mod axum_impl {
+    use axum::{
+        body::Bytes,
+        extract::{FromRequest, Request},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    use serde::de::DeserializeOwned;
+
+    use super::{from_body, Rejection, SanitizedJson, StrictJson};
+    use crate::{CowStr, SanitizedString};
+
+    impl IntoResponse for Rejection {
+        fn into_response(self) -> Response {
+            let status = if self.is_syntax() {
+                StatusCode::BAD_REQUEST
+            } else {
+                StatusCode::UNPROCESSABLE_ENTITY
+            };
+            (status, self.to_string()).into_response()
+        }
+    }
+
+    impl<T, S> FromRequest<S> for SanitizedJson<T>
+    where
+        T: DeserializeOwned,
+        S: Send + Sync,
+    {
+        type Rejection = Response;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let body = Bytes::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            from_body(&body, false)
+                .map(SanitizedJson)
+                .map_err(IntoResponse::into_response)
+        }
+    }
+
+    impl<T, S> FromRequest<S> for StrictJson<T>
+    where
+        T: DeserializeOwned,
+        S: Send + Sync,
+    {
+        type Rejection = Response;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let body = Bytes::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            from_body(&body, true)
+                .map(StrictJson)
+                .map_err(IntoResponse::into_response)
+        }
+    }
+
+    /// A UTF-8 request body, sanitized with the active policy.
+    impl<S> FromRequest<S> for SanitizedString
+    where
+        S: Send + Sync,
+    {
+        type Rejection = Response;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let body = String::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            Ok(CowStr::from(body))
+        }
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix_impl {
+    use std::{future::Future, pin::Pin};
+
+    use actix_web::{
+        body::BoxBody,
+        dev::Payload,
+        http::{header::ContentType, StatusCode},
+        web::Bytes,
+        FromRequest, HttpRequest, HttpResponse, Responder, ResponseError,
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{from_body, Rejection, SanitizedJson, StrictJson};
+    use crate::{sanitize_json, CowStr, SanitizedString};
+
+    type LocalFuture<T> = Pin<Box<dyn Future<Output = Result<T, actix_web::Error>>>>;
+
+    impl ResponseError for Rejection {
+        fn status_code(&self) -> StatusCode {
+            if self.is_syntax() {
+                StatusCode::BAD_REQUEST
+            } else {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+        }
+    }
+
+    impl<T> FromRequest for SanitizedJson<T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        type Error = actix_web::Error;
+        type Future = LocalFuture<Self>;
+
+        fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+            let body = Bytes::from_request(req, payload);
+            Box::pin(async move { Ok(SanitizedJson(from_body(&body.await?, false)?)) })
+        }
+    }
+
+    impl<T> FromRequest for StrictJson<T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        type Error = actix_web::Error;
+        type Future = LocalFuture<Self>;
+
+        fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+            let body = Bytes::from_request(req, payload);
+            Box::pin(async move { Ok(StrictJson(from_body(&body.await?, true)?)) })
+        }
+    }
+
+    /// A UTF-8 request body, sanitized with the active policy.
+    impl FromRequest for SanitizedString {
+        type Error = actix_web::Error;
+        type Future = LocalFuture<Self>;
+
+        fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+            let body = String::from_request(req, payload);
+            Box::pin(async move { Ok(CowStr::from(body.await?)) })
+        }
+    }
+
+    /// Responds with `T` as JSON with every string, including object keys,
+    /// sanitized with the active policy.
+    impl<T> Responder for SanitizedJson<T>
+    where
+        T: Serialize,
+    {
+        type Body = BoxBody;
+
+        fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+            match serde_json::to_value(self.0) {
+                Ok(mut value) => {
+                    sanitize_json(&mut value, true);
+                    HttpResponse::Ok().json(value)
+                }
+                Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+            }
+        }
+    }
+
+    /// Responds with the string as `text/plain`.
+    impl Responder for CowStr<'_> {
+        type Body = BoxBody;
+
+        fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+            HttpResponse::Ok()
+                .content_type(ContentType::plaintext())
+                .body(self.into_owned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Message {
+        role: String,
+        content: Vec<String>,
+    }
+
+    #[test]
+    fn test_from_body() {
+        let body = br#"{"role": "user", "content": ["hi", "there"]}"#;
+        let expected = Message {
+            role: "user".into(),
+            content: vec!["hi".into(), "there".into()],
+        };
+        assert_eq!(from_body::<Message>(body, true).unwrap(), expected);
+
+        let body = r#"{"role": "user🏴", "content": ["hi", "there🏴"]}"#.as_bytes();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(from_body::<Message>(body, false).unwrap(), expected);
+        assert!(matches!(
+            from_body::<Message>(body, true),
+            Err(Rejection::Disallowed(Error::Disallowed { .. }))
+        ));
+
+        assert!(from_body::<Message>(b"{", false).unwrap_err().is_syntax());
+        assert!(matches!(
+            from_body::<Message>(b"{}", false),
+            Err(Rejection::Data(_))
+        ));
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn test_axum() {
+        use axum::{
+            body::Body,
+            extract::{FromRequest, Request},
+            http::StatusCode,
+        };
+
+        use crate::SanitizedString;
+
+        let request = |body: &'static str| Request::new(Body::from(body));
+
+        let SanitizedJson(message) = SanitizedJson::<Message>::from_request(
+            request(r#"{"role": "user", "content": ["hi🏴"]}"#),
+            &(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.role, "user");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(message.content, ["hi"]);
+
+        let rejection = StrictJson::<Message>::from_request(
+            request(r#"{"role": "user", "content": ["hi🏴"]}"#),
+            &(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let rejection = SanitizedJson::<Message>::from_request(request("nope"), &())
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.status(), StatusCode::BAD_REQUEST);
+
+        let text = SanitizedString::from_request(request("hello🏴"), &())
+            .await
+            .unwrap();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(&*text, "hello");
+        assert!(text.is_owned());
+    }
+
+    #[cfg(feature = "actix")]
+    #[tokio::test]
+    async fn test_actix() {
+        use actix_web::{
+            body::to_bytes, http::StatusCode, test::TestRequest, FromRequest, Responder,
+        };
+
+        use crate::{CowStr, SanitizedString};
+
+        let (req, mut payload) = TestRequest::default()
+            .set_payload(r#"{"role": "user", "content": ["hi🏴"]}"#)
+            .to_http_parts();
+        let SanitizedJson(message) = SanitizedJson::<Message>::from_request(&req, &mut payload)
+            .await
+            .unwrap();
+        assert_eq!(message.role, "user");
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(message.content, ["hi"]);
+
+        let (req, mut payload) = TestRequest::default()
+            .set_payload(r#"{"role": "user", "content": ["hi🏴"]}"#)
+            .to_http_parts();
+        let error = StrictJson::<Message>::from_request(&req, &mut payload)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.as_response_error().status_code(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let (req, mut payload) = TestRequest::default()
+            .set_payload("hello🏴")
+            .to_http_parts();
+        let text = SanitizedString::from_request(&req, &mut payload)
+            .await
+            .unwrap();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(&*text, "hello");
+        assert!(text.starts_with("hello"));
+
+        let req = TestRequest::default().to_http_request();
+        let response = SanitizedJson(serde_json::json!({"a🏴": "b🏴"})).respond_to(&req);
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body()).await.unwrap();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(body, r#"{"a":"b"}"#);
+        assert!(!body.is_empty());
+
+        let response = CowStr::new("hi🏴").respond_to(&req);
+        let body = to_bytes(response.into_body()).await.unwrap();
+        #[cfg(not(feature = "verbose"))]
+        assert_eq!(body, "hi");
+        assert!(!body.is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+//! Regression tests against a versioned corpus of published prompt injection
+//! and smuggling samples, so a policy change can't silently weaken protection.
+//! Each sample in `attack-corpus.json` records what a policy of printable
+//! ASCII sanitizes it to, `null` if nothing, and what `analyze` detects in it.
+use std::collections::HashSet;
+
+use langsan::{analyze_with, sanitize_with, Policy};
+use serde_json::Value;
+
+/// The corpus, with astral characters escaped as surrogate pairs.
+const CORPUS: &str = include_str!("attack-corpus.json");
+/// The corpus version this harness understands. Bump both when the format
+/// changes.
+const VERSION: u64 = 1;
+
+#[test]
+fn attack_corpus() {
+    let corpus: Value = serde_json::from_str(CORPUS).unwrap();
+    assert_eq!(corpus["version"], VERSION, "unsupported corpus version");
+    let policy = Policy::new(vec![0x0A..=0x0A, 0x20..=0x7E]).verbose(false);
+
+    let mut ids = HashSet::new();
+    let mut failures = Vec::new();
+    let samples = corpus["samples"].as_array().unwrap();
+    for sample in samples {
+        let id = sample["id"].as_str().unwrap();
+        assert!(ids.insert(id), "duplicate sample {id}");
+        let input = sample["input"].as_str().unwrap();
+
+        let sanitized = sanitize_with(input, &policy);
+        let expected = sample["sanitized"].as_str();
+        if sanitized.as_deref() != expected {
+            failures.push(format!(
+                "{id}: sanitized to {sanitized:?}, expected {expected:?}"
+            ));
+        }
+
+        let mut detections: Vec<String> = analyze_with(input, &policy)
+            .findings()
+            .iter()
+            .map(|finding| format!("{:?}", finding.detection))
+            .collect();
+        detections.sort();
+        detections.dedup();
+        let expected: Vec<&str> = sample["detections"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|detection| detection.as_str().unwrap())
+            .collect();
+        if detections != expected {
+            failures.push(format!(
+                "{id}: detected {detections:?}, expected {expected:?}"
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} samples regressed:\n{}",
+        failures.len(),
+        samples.len(),
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,94 @@
+//! `#[derive(Sanitize)]`, with per-field named policies.
+#![cfg(feature = "derive")]
+
+use std::sync::Once;
+
+use langsan::{register_policy, Error, Policy, Sanitize};
+
+#[derive(Debug, Sanitize)]
+struct Message {
+    role: String,
+    content: Option<String>,
+}
+
+#[derive(Debug, Sanitize)]
+struct Snippet {
+    #[langsan(policy = "code")]
+    code: String,
+    #[langsan(policy = "chat_input")]
+    comment: Option<String>,
+    messages: Vec<Message>,
+    #[langsan(skip)]
+    line: u32,
+}
+
+#[derive(Debug, Sanitize)]
+struct Wrapper<T>(T, #[langsan(skip)] f32);
+
+#[derive(Sanitize)]
+struct Unregistered {
+    #[langsan(policy = "unregistered")]
+    text: String,
+}
+
+/// Register the policies the tests use, once for every test.
+fn register() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let ascii = Policy::new(vec![0x0A..=0x0A, 0x20..=0x7E]).verbose(false);
+        register_policy("code", ascii.clone());
+        register_policy("chat_input", ascii.forbid(['<', '>']));
+    });
+}
+
+#[test]
+fn derive_sanitize() {
+    register();
+    let mut snippet = Snippet {
+        code: "Vec<u8>".into(),
+        comment: Some("bytes <b>é</b>".into()),
+        messages: vec![Message {
+            role: "user".into(),
+            content: Some("hi\u{E0041}".into()),
+        }],
+        line: 7,
+    };
+    assert_eq!(
+        snippet.sanitize_strict(),
+        Err(Error::Disallowed { offset: 6, c: '<' })
+    );
+    assert_eq!(snippet.sanitize(), 2);
+    assert_eq!(snippet.sanitize_strict(), Ok(()));
+    assert_eq!(snippet.code, "Vec<u8>");
+    assert_eq!(snippet.comment.as_deref(), Some("bytes "));
+    #[cfg(not(feature = "verbose"))]
+    assert_eq!(snippet.messages[0].content.as_deref(), Some("hi"));
+    assert_eq!(snippet.line, 7);
+
+    let mut wrapper = Wrapper(snippet, 1.0);
+    assert_eq!(wrapper.sanitize(), 0);
+    wrapper.0.code.push('é');
+    assert_eq!(wrapper.try_sanitize(), Ok(1));
+    assert_eq!(wrapper.0.code, "Vec<u8>");
+    assert_eq!(wrapper.1, 1.0);
+}
+
+#[test]
+fn derive_unregistered_policy() {
+    let mut unregistered = Unregistered { text: "hi".into() };
+    assert!(matches!(
+        unregistered.sanitize_strict(),
+        Err(Error::InvalidPolicy(_))
+    ));
+    // Including when nested, without panicking
+    let mut nested = Wrapper(vec![Unregistered { text: "hi".into() }], 1.0);
+    assert!(matches!(
+        nested.try_sanitize(),
+        Err(Error::InvalidPolicy(_))
+    ));
+    let panic = std::panic::catch_unwind(move || unregistered.sanitize()).unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().unwrap(),
+        "invalid policy: no policy is registered as \"unregistered\""
+    );
+}
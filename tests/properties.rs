@@ -0,0 +1,92 @@
+//! Property tests for the sanitizer's invariants, with strategies for the kinds
+//! of adversarial strings it exists to catch.
+use langsan::{
+    sanitize, sanitize_outcome, sanitize_outcome_with, sanitize_strict, sanitize_with, Policy,
+};
+use proptest::prelude::*;
+
+/// Unicode tag characters, which can smuggle invisible ASCII.
+fn tag_char() -> impl Strategy<Value = char> {
+    (0xE0000u32..=0xE007F).prop_map(|c| char::from_u32(c).unwrap())
+}
+
+/// Bidirectional controls and other invisible formatting characters.
+fn bidi_char() -> impl Strategy<Value = char> {
+    prop::sample::select(vec![
+        '\u{061C}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{200E}', '\u{200F}', '\u{202A}',
+        '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', '\u{2066}', '\u{2067}', '\u{2068}',
+        '\u{2069}', '\u{FEFF}',
+    ])
+}
+
+/// Characters from a mix of scripts, mostly ones enabled by some feature.
+fn script_char() -> impl Strategy<Value = char> {
+    prop_oneof![
+        4 => prop::char::range(' ', '~'),
+        1 => prop::char::range('\t', '\r'),
+        1 => prop::char::range('\u{80}', '\u{24F}'),
+        1 => prop::char::range('\u{370}', '\u{4FF}'),
+        1 => prop::char::range('\u{590}', '\u{6FF}'),
+        1 => prop::char::range('\u{3040}', '\u{30FF}'),
+        1 => prop::char::range('\u{4E00}', '\u{9FFF}'),
+        1 => prop::char::range('\u{1F300}', '\u{1F64F}'),
+        1 => Just('🏴'),
+    ]
+}
+
+/// Strings mixing ordinary text from several scripts with tag characters and
+/// bidi controls.
+fn adversarial() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![8 => script_char(), 1 => tag_char(), 1 => bidi_char()],
+        0..64,
+    )
+    .prop_map(String::from_iter)
+}
+
+proptest! {
+    #[test]
+    fn sanitize_is_idempotent(s in adversarial()) {
+        let once = sanitize(&s).unwrap_or_else(|| s.clone());
+        prop_assert_eq!(sanitize(&once), None);
+    }
+
+    #[test]
+    fn output_is_clean(s in adversarial()) {
+        let outcome = sanitize_outcome(&s);
+        prop_assert!(sanitize_strict(outcome.as_str()).is_ok());
+    }
+
+    #[test]
+    fn clean_input_is_unmodified(s in adversarial()) {
+        prop_assert_eq!(sanitize_strict(&s).is_ok(), sanitize(&s).is_none());
+    }
+
+    #[test]
+    fn any_string_is_handled(s in any::<String>()) {
+        let outcome = sanitize_outcome(&s);
+        prop_assert!(sanitize_strict(outcome.as_str()).is_ok());
+    }
+
+    #[test]
+    fn only_the_reported_span_is_removed(s in adversarial()) {
+        let outcome = sanitize_outcome(&s);
+        let Some(report) = outcome.report() else {
+            return Ok(());
+        };
+        let removed = report.removed().unwrap();
+        prop_assert!(s.is_char_boundary(removed.start) && s.is_char_boundary(removed.end));
+        prop_assert_eq!(report.removed_bytes(), removed.len());
+        prop_assert!(outcome.as_str().starts_with(&s[..removed.start]));
+        prop_assert!(outcome.as_str().ends_with(&s[removed.end..]));
+        #[cfg(not(feature = "verbose"))]
+        prop_assert_eq!(outcome.as_str().len(), s.len() - removed.len());
+    }
+
+    #[test]
+    fn custom_policies_hold(s in adversarial(), forbid in prop::collection::vec(prop::char::range('a', 'z'), 0..4)) {
+        let policy = Policy::new(vec![0x20..=0x7E]).forbid(forbid);
+        let once = sanitize_with(&s, &policy).unwrap_or_else(|| s.clone());
+        prop_assert!(sanitize_outcome_with(&once, &policy).is_clean());
+    }
+}
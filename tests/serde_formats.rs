@@ -0,0 +1,71 @@
+//! `CowStr` with formats that are not self-describing.
+#![cfg(feature = "serde")]
+
+use langsan::CowStr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message<'a> {
+    id: u32,
+    content: CowStr<'a>,
+    #[serde(borrow, deserialize_with = "CowStr::deserialize_borrowed")]
+    borrowed: CowStr<'a>,
+    tags: Vec<CowStr<'a>>,
+}
+
+fn message() -> Message<'static> {
+    Message {
+        id: 42,
+        content: "Hello, world!".into(),
+        borrowed: "zero copy".into(),
+        tags: vec!["a".into(), "b".into()],
+    }
+}
+
+/// Serialize a plain `String` where a `CowStr` is expected, to check that
+/// deserialization still sanitizes.
+#[derive(Serialize)]
+struct Raw<'a> {
+    id: u32,
+    content: &'a str,
+    borrowed: &'a str,
+    tags: Vec<&'a str>,
+}
+
+const RAW: Raw = Raw {
+    id: 1,
+    content: "Hi🏴!",
+    borrowed: "🏴",
+    tags: vec![],
+};
+
+#[test]
+fn test_bincode() {
+    let bytes = bincode::serialize(&message()).unwrap();
+    let decoded: Message = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, message());
+    assert!(decoded.borrowed.is_borrowed());
+
+    let bytes = bincode::serialize(&RAW).unwrap();
+    let decoded: Message = bincode::deserialize(&bytes).unwrap();
+    assert!(decoded.borrowed.is_owned());
+    #[cfg(not(feature = "verbose"))]
+    assert_eq!(decoded.content.as_ref(), "Hi!");
+    #[cfg(not(feature = "verbose"))]
+    assert_eq!(decoded.borrowed.as_ref(), "");
+}
+
+#[test]
+fn test_postcard() {
+    let bytes = postcard::to_allocvec(&message()).unwrap();
+    let decoded: Message = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, message());
+    assert!(decoded.borrowed.is_borrowed());
+
+    let bytes = postcard::to_allocvec(&RAW).unwrap();
+    let decoded: Message = postcard::from_bytes(&bytes).unwrap();
+    #[cfg(not(feature = "verbose"))]
+    assert_eq!(decoded.content.as_ref(), "Hi!");
+    #[cfg(feature = "verbose")]
+    assert_eq!(decoded.content.as_ref(), "Hi[4 BYTES SANITIZED]!");
+}